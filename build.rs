@@ -29,7 +29,7 @@ fn main() -> Result<(), Error> {
 
     for dm in departments {
         let cops = cops.iter().filter(|c| dm == c["department_mod"]);
-        let cops = cops.map(|c| c.clone()).collect::<Vec<_>>();
+        let cops = cops.cloned().collect::<Vec<_>>();
 
         render_with_cops(
             "codegen/cop/mod.liquid",
@@ -38,7 +38,7 @@ fn main() -> Result<(), Error> {
         )?;
 
         render_with_cops(
-            "codegen/cop/mod.liquid",
+            "codegen/cop/mod_tests.liquid",
             &format!("tests/cop/{dm}/mod.rs"),
             &cops,
         )?;
@@ -51,13 +51,13 @@ fn main() -> Result<(), Error> {
         render_single_cop(
             "codegen/new_cop.liquid",
             &format!("src/cop/{dm}/{sn}.rs"),
-            &cop,
+            cop,
         )?;
 
         render_single_cop(
             "codegen/new_cop_tests.liquid",
             &format!("tests/cop/{dm}/{sn}.rs"),
-            &cop,
+            cop,
         )?;
     }
 
@@ -0,0 +1,158 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::cop::{self, Name, Offense};
+use crate::source::DecodedInput;
+use crate::{path_util, Config, ConfigStore, AUTO_GENERATED_FILE, DOTFILE};
+
+pub const DEFAULT_EXCLUDE_LIMIT: usize = 15;
+
+#[derive(Default)]
+struct CopOffenses {
+    count: usize,
+    correctable: bool,
+    files: BTreeSet<String>,
+}
+
+// Writes `.rubocop_todo.yml` to `dir`, disabling each cop with offenses or
+// excluding the files in which they were found, and makes `.rubocop.yml`
+// inherit from it. Progress is reported on stderr, where it doesn't get
+// mixed up with the output of formatters.
+pub fn auto_gen_config(
+    dir: &Path,
+    files: &[(DecodedInput, Vec<Offense>)],
+    exclude_limit: Option<usize>,
+) -> std::io::Result<()> {
+    let contents = todo_file_contents(dir, files, exclude_limit);
+    std::fs::write(dir.join(AUTO_GENERATED_FILE), contents)?;
+    eprintln!("Created {AUTO_GENERATED_FILE}.");
+
+    if add_inheritance_from_auto_generated_file(dir)? {
+        eprintln!("Added inheritance from `{AUTO_GENERATED_FILE}` in `{DOTFILE}`.");
+    }
+    Ok(())
+}
+
+pub fn todo_file_contents(
+    dir: &Path,
+    files: &[(DecodedInput, Vec<Offense>)],
+    exclude_limit: Option<usize>,
+) -> String {
+    let mut cops: BTreeMap<&str, CopOffenses> = BTreeMap::new();
+    let dir = path_util::absolute_path(dir);
+    let syntax = cop::lint::Syntax.name();
+    for (input, offenses) in files {
+        let path = path_util::absolute_path(Path::new(&input.name));
+        let path = path.strip_prefix(&dir).unwrap_or(&path);
+        // Disabling `Lint/Syntax` would hide files that can't be parsed.
+        let offenses = offenses.iter().filter(|o| o.cop_name != syntax);
+        for offense in offenses.filter(|o| !o.disabled && !o.corrected) {
+            let cop = cops.entry(offense.cop_name).or_default();
+            cop.count += 1;
+            cop.correctable |= offense.correctable;
+            cop.files.insert(path.to_string_lossy().into_owned());
+        }
+    }
+
+    let mut command = String::from("rubocop --auto-gen-config");
+    if let Some(exclude_limit) = exclude_limit {
+        command.push_str(&format!(" --exclude-limit {exclude_limit}"));
+    }
+    let version = env!("CARGO_PKG_VERSION");
+
+    let mut contents = format!(
+        "\
+# This configuration was generated by
+# `{command}`
+# using RuboCop version {version}.
+# The point is for the user to remove these configuration records
+# one by one as the offenses are removed from the code base.
+# Note that changes in the inspected code, or installation of new
+# versions of RuboCop, may require this file to be generated again.
+"
+    );
+
+    // Whether corrections are safe depends on the configuration of the
+    // project, apart from the generated file itself.
+    let config_store = ConfigStore::new(None).with_auto_gen_config(true);
+    let yaml = config_store.for_dir(&dir).ok().flatten();
+    let config = Config::new(yaml.as_deref());
+
+    let exclude_limit = exclude_limit.unwrap_or(DEFAULT_EXCLUDE_LIMIT);
+    for (cop_name, cop) in cops {
+        contents.push_str(&format!("\n# Offense count: {}\n", cop.count));
+        if cop.correctable {
+            let mut all_cops = crate::default::cops().iter();
+            let base = all_cops.find(|base| base.name() == cop_name);
+            let support = if base.is_some_and(|&base| config.is_safe_autocorrect(base)) {
+                "safe autocorrection (--autocorrect)"
+            } else {
+                "unsafe autocorrection (--autocorrect-all)"
+            };
+            contents.push_str(&format!("# This cop supports {support}.\n"));
+        }
+        contents.push_str(&format!("{cop_name}:\n"));
+        if cop.files.len() > exclude_limit {
+            contents.push_str("  Enabled: false\n");
+        } else {
+            contents.push_str("  Exclude:\n");
+            for file in cop.files {
+                let file = file.replace('\'', "''");
+                contents.push_str(&format!("    - '{file}'\n"));
+            }
+        }
+    }
+
+    contents
+}
+
+// Returns whether `.rubocop.yml` was changed.
+fn add_inheritance_from_auto_generated_file(dir: &Path) -> std::io::Result<bool> {
+    let path = dir.join(DOTFILE);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut lines = contents.lines().map(String::from).collect::<Vec<_>>();
+    let inherit_from = lines.iter().position(|l| l.starts_with("inherit_from:"));
+    match inherit_from {
+        Some(index) => {
+            let mut inherited = Vec::new();
+            let mut end = index + 1;
+            let value = lines[index]["inherit_from:".len()..].trim();
+            if value.is_empty() {
+                while let Some(item) = lines.get(end).and_then(|l| l.trim().strip_prefix('-')) {
+                    inherited.push(item.trim().to_string());
+                    end += 1;
+                }
+            } else {
+                let value = value.trim_start_matches('[').trim_end_matches(']');
+                inherited.extend(value.split(',').map(|s| s.trim().to_string()));
+            }
+
+            let mut files = inherited.iter().map(|s| s.trim_matches(['\'', '"']));
+            if files.any(|s| s == AUTO_GENERATED_FILE) {
+                return Ok(false);
+            }
+
+            let mut replacement = vec!["inherit_from:".to_string()];
+            replacement.push(format!("  - {AUTO_GENERATED_FILE}"));
+            replacement.extend(inherited.iter().map(|s| format!("  - {s}")));
+            lines.splice(index..end, replacement);
+        }
+        None => {
+            let header = [
+                format!("inherit_from: {AUTO_GENERATED_FILE}"),
+                String::new(),
+            ];
+            lines.splice(0..0, header);
+        }
+    }
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    std::fs::write(path, contents)?;
+    Ok(true)
+}
@@ -0,0 +1,221 @@
+use lib_ruby_parser::source::{Comment, CommentType};
+use lib_ruby_parser::Loc;
+
+use crate::source::DecodedInput;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DirectiveMode {
+    Disable,
+    Enable,
+    Todo,
+}
+
+// A `# rubocop:disable`, `# rubocop:enable` or `# rubocop:todo` comment.
+#[derive(Debug)]
+pub struct DirectiveComment {
+    pub loc: Loc,
+    pub line: usize,
+    pub mode: DirectiveMode,
+    pub cop_names: Vec<String>,
+    // Whether the comment follows code on the same line, in which case it
+    // only applies to that line.
+    pub single_line: bool,
+}
+
+impl DirectiveComment {
+    pub fn parse(text: &str) -> Option<(DirectiveMode, Vec<String>)> {
+        let text = text.strip_prefix('#')?.trim_start();
+        let text = text.strip_prefix("rubocop")?.trim_start();
+        let text = text.strip_prefix(':')?.trim_start();
+
+        let (mode, text) = text.split_once(char::is_whitespace)?;
+        let mode = match mode {
+            "disable" => DirectiveMode::Disable,
+            "enable" => DirectiveMode::Enable,
+            "todo" => DirectiveMode::Todo,
+            _ => return None,
+        };
+
+        // Only the leading comma-separated names are used, so that they can be
+        // followed by a comment, e.g. `-- reason`.
+        let mut cop_names = Vec::new();
+        let mut text = text;
+        loop {
+            let rest = text.trim_start();
+            let invalid = |c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '/';
+            let (name, rest) = rest.split_at(rest.find(invalid).unwrap_or(rest.len()));
+            if name.is_empty() {
+                break;
+            }
+            cop_names.push(name.to_string());
+            let Some(rest) = rest.trim_start().strip_prefix(',') else { break };
+            text = rest;
+        }
+        if cop_names.is_empty() {
+            return None;
+        }
+        Some((mode, cop_names))
+    }
+
+    pub fn is_disabling(&self) -> bool {
+        self.mode != DirectiveMode::Enable
+    }
+}
+
+// Lines on which a name from a directive, i.e. `all`, a department or a
+// cop, is disabled. `end_line` is `None` if the name is never enabled again.
+#[derive(Debug)]
+pub struct DisabledRange {
+    pub name: String,
+    pub directive: usize,
+    pub begin_line: usize,
+    pub end_line: Option<usize>,
+    // Cops or departments enabled again while `all` or their department is
+    // still disabled.
+    pub enabled_ranges: Vec<EnabledRange>,
+}
+
+impl DisabledRange {
+    pub fn contains(&self, line: usize) -> bool {
+        line >= self.begin_line && self.end_line.is_none_or(|end| line <= end)
+    }
+
+    pub fn disables(&self, cop_name: &str, line: usize) -> bool {
+        let mut enabled_ranges = self.enabled_ranges.iter();
+        self.applies_to(cop_name)
+            && self.contains(line)
+            && !enabled_ranges.any(|r| r.applies_to(cop_name) && r.contains(line))
+    }
+
+    // The cops reporting directives must be disabled explicitly, since they
+    // would otherwise be silenced by the very directives they report.
+    pub fn applies_to(&self, cop_name: &str) -> bool {
+        let department = cop_name.split_once('/').map(|(d, _)| d);
+        match self.name.as_str() {
+            "all" => !matches!(
+                cop_name,
+                "Lint/RedundantCopDisableDirective" | "Lint/MissingCopEnableDirective"
+            ),
+            name => name == cop_name || Some(name) == department,
+        }
+    }
+}
+
+// Lines after a `# rubocop:enable` of a name inside a wider disabled range,
+// up to a later `# rubocop:disable` of the same name.
+#[derive(Debug)]
+pub struct EnabledRange {
+    pub name: String,
+    pub begin_line: usize,
+    pub end_line: Option<usize>,
+}
+
+impl EnabledRange {
+    pub fn contains(&self, line: usize) -> bool {
+        line > self.begin_line && self.end_line.is_none_or(|end| line < end)
+    }
+
+    pub fn applies_to(&self, cop_name: &str) -> bool {
+        let department = cop_name.split_once('/').map(|(d, _)| d);
+        self.name == cop_name || Some(self.name.as_str()) == department
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CommentConfig {
+    pub directives: Vec<DirectiveComment>,
+    pub disabled_ranges: Vec<DisabledRange>,
+}
+
+impl CommentConfig {
+    pub fn new(input: &DecodedInput, comments: &[Comment]) -> Self {
+        let mut config = Self::default();
+
+        for comment in comments {
+            if comment.kind != CommentType::Inline {
+                continue;
+            }
+            // Comment locations include the line break.
+            let text = input.source(comment.location);
+            let text = text.trim_end();
+            let Some((mode, cop_names)) = DirectiveComment::parse(text) else { continue };
+            let loc = Loc {
+                begin: comment.location.begin,
+                end: comment.location.begin + text.len(),
+            };
+
+            let line_begin = input.bytes[..loc.begin].iter().rposition(|&b| b == b'\n');
+            let line_begin = line_begin.map_or(0, |pos| pos + 1);
+            let before = &input.bytes[line_begin..loc.begin];
+            let single_line = !before.iter().all(u8::is_ascii_whitespace);
+
+            let (line, _) = input.line_col_for_pos(loc.begin);
+            config.add_directive(DirectiveComment {
+                loc,
+                line,
+                mode,
+                cop_names,
+                single_line,
+            });
+        }
+
+        config
+    }
+
+    fn add_directive(&mut self, directive: DirectiveComment) {
+        let index = self.directives.len();
+        let line = directive.line;
+
+        for name in directive.cop_names.iter() {
+            let is_open = |r: &DisabledRange| r.end_line.is_none() && r.name == *name;
+
+            let ranges = self.disabled_ranges.iter_mut();
+            let open_ranges = ranges.filter(|r| r.end_line.is_none());
+            if directive.mode == DirectiveMode::Enable {
+                for range in open_ranges {
+                    let department = range.name.split_once('/').map(|(d, _)| d);
+                    if name == "all" || range.name == *name || department == Some(name) {
+                        range.end_line = Some(line);
+                    } else if range.applies_to(name) {
+                        let mut enabled_ranges = range.enabled_ranges.iter();
+                        if !enabled_ranges.any(|r| r.name == *name && r.end_line.is_none()) {
+                            range.enabled_ranges.push(EnabledRange {
+                                name: name.clone(),
+                                begin_line: line,
+                                end_line: None,
+                            });
+                        }
+                    }
+                }
+                continue;
+            }
+
+            if !directive.single_line {
+                for range in open_ranges {
+                    let enabled_ranges = range.enabled_ranges.iter_mut();
+                    for enabled in enabled_ranges.filter(|r| r.end_line.is_none()) {
+                        if enabled.name == *name {
+                            enabled.end_line = Some(line);
+                        }
+                    }
+                }
+            }
+            if directive.single_line || !self.disabled_ranges.iter().any(is_open) {
+                self.disabled_ranges.push(DisabledRange {
+                    name: name.clone(),
+                    directive: index,
+                    begin_line: line,
+                    end_line: directive.single_line.then_some(line),
+                    enabled_ranges: Vec::new(),
+                });
+            }
+        }
+
+        self.directives.push(directive);
+    }
+
+    pub fn is_cop_disabled_line(&self, cop_name: &str, line: usize) -> bool {
+        let mut ranges = self.disabled_ranges.iter();
+        ranges.any(|r| r.disables(cop_name, line))
+    }
+}
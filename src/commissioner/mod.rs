@@ -7,9 +7,9 @@ use lib_ruby_parser::traverse::visitor::*;
 use lib_ruby_parser::{Parser, ParserResult};
 
 use crate::ast::Processor;
-use crate::cop::{Base, Context, Offense};
+use crate::cop::{self, Base, Context, Name, Offense};
 use crate::source::{Corrector, DecodedInput};
-use crate::Config;
+use crate::{debug, Autocorrect, CommentConfig, Config};
 
 pub struct Commissioner<'cop, 'cfg, 'ast> {
     cop: &'cop dyn Base,
@@ -21,38 +21,79 @@ impl Commissioner<'_, '_, '_> {
     pub fn investigate(
         cops: &[&dyn Base],
         config: Config,
+        autocorrect: Autocorrect,
         parser: Parser,
     ) -> (DecodedInput, Vec<Offense>, Corrector) {
-        let ParserResult { input, ast, .. } = parser.do_parse();
+        let ParserResult {
+            input,
+            ast,
+            comments,
+            diagnostics,
+            ..
+        } = parser.do_parse();
         let input = DecodedInput::from(input);
 
-        let Some(ast) = ast else {
-            let corrector = Corrector::new(input.as_shared_bytes());
-            return (input, Vec::new(), corrector)
-        };
-
         let mut processor = Processor {
             parents: HashMap::new(),
         };
-        processor.process(&ast);
+        if let Some(ast) = &ast {
+            processor.process(ast);
+        }
         let Processor { parents } = processor;
 
+        // Only syntax errors are reported for source that can't be parsed.
+        let syntax = cop::lint::Syntax.name();
+        let valid_syntax = ast.is_some() && !diagnostics.iter().any(|d| d.is_error());
+        let cops = cops
+            .iter()
+            .filter(|cop| valid_syntax || cop.name() == syntax);
+
         let mut corrector = Corrector::new(input.as_shared_bytes());
-        let mut ctx = Context::new(config, input, parents);
+        let comment_config = CommentConfig::new(&input, &comments);
+        let mut ctx = Context::new(config, input, comment_config, diagnostics, parents);
+        let mut uncorrected = Vec::new();
 
-        for &cop in cops.iter() {
+        // Redundant directives can only be found once all other cops have
+        // added their offenses.
+        let redundant_directive = cop::lint::RedundantCopDisableDirective.name();
+        let (last, cops): (Vec<_>, Vec<_>) =
+            cops.partition(|cop| cop.name() == redundant_directive);
+
+        for &cop in cops.into_iter().chain(last) {
             if ctx.is_cop_enabled(cop) {
+                let mut cop_corrector = Corrector::new(ctx.input().as_shared_bytes());
+                cop.on_new_investigation(&mut ctx, &mut cop_corrector);
                 let mut commissioner = Commissioner {
                     cop,
                     ctx,
-                    corrector,
+                    corrector: cop_corrector,
                 };
-                commissioner.visit(&ast);
-                Commissioner { ctx, corrector, .. } = commissioner;
+                if let Some(ast) = &ast {
+                    commissioner.visit(ast);
+                }
+                ctx = commissioner.ctx;
+                let mut cop_corrector = commissioner.corrector;
+                cop.on_investigation_end(&mut ctx, &mut cop_corrector);
+
+                // Unsafe corrections are only applied with `-A`, and those
+                // that clash with corrections of a previous cop are left for
+                // the next pass of the autocorrect loop.
+                if autocorrect == Autocorrect::Safe && !ctx.is_safe_autocorrect(cop) {
+                    uncorrected.push(cop.name());
+                } else if let Err(error) = corrector.merge(cop_corrector) {
+                    let name = &ctx.input().name;
+                    let cop_name = cop.name();
+                    debug!("Skipping corrections of {cop_name} in {name}: {error}");
+                    uncorrected.push(cop_name);
+                }
             }
         }
 
-        let (input, offenses) = ctx.into_inner();
+        let (input, mut offenses) = ctx.into_inner();
+        for offense in offenses.iter_mut() {
+            let uncorrected = uncorrected.contains(&offense.cop_name);
+            offense.corrected &= autocorrect.is_enabled() && !offense.disabled && !uncorrected;
+        }
         (input, offenses, corrector)
     }
 }
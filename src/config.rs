@@ -1,43 +1,168 @@
-use crate::cop;
+use std::path::Path;
 
-use serde_yaml::{Index, Value};
+use crate::cop::{self, Name};
+use crate::path_util;
 
-// TODO: support department config
+use serde_yaml::{Index, Value};
 
 pub struct Config<'a> {
     pub yaml: Option<&'a Value>,
+    base_dir: Option<&'a Path>,
+    yaml_department: Option<&'a Value>,
     default: &'static Value,
+    default_department: Option<&'static Value>,
 }
 
 impl<'a> Config<'a> {
     pub fn new(yaml: Option<&'a Value>) -> Self {
         Self {
             yaml,
+            base_dir: None,
+            yaml_department: None,
             default: crate::default::config(),
+            default_department: None,
         }
     }
 
+    pub fn with_base_dir(self, base_dir: &'a Path) -> Self {
+        Self {
+            base_dir: Some(base_dir),
+            ..self
+        }
+    }
+
+    // Replaces the default configuration, which settings not found in the
+    // configuration files fall back to.
+    pub fn with_default(self, default: &'static Value) -> Self {
+        Self { default, ..self }
+    }
+
+    // Settings of a cop, in its configuration or the default one, take
+    // precedence over those of its department, e.g. `Style` for `Style/Not`.
     pub fn for_cop(&self, cop: &dyn cop::Base) -> Self {
+        let name = cop.name();
+        let department = name.split_once('/').map_or(name, |(d, _)| d);
         Self {
-            yaml: self.yaml.map(|v| v.get(cop.name())).flatten(),
-            default: &self.default[cop.name()],
+            yaml: self.yaml.and_then(|v| v.get(name)),
+            base_dir: self.base_dir,
+            yaml_department: self.yaml.and_then(|v| v.get(department)),
+            default: &self.default[name],
+            default_department: self.default.get(department),
         }
     }
 
     pub fn for_all_cops(&self) -> Self {
         Self {
-            yaml: self.yaml.map(|v| v.get("AllCops")).flatten(),
+            yaml: self.yaml.and_then(|v| v.get("AllCops")),
+            base_dir: self.base_dir,
+            yaml_department: None,
             default: &self.default["AllCops"],
+            default_department: None,
         }
     }
 
     pub fn get<I: Index>(&self, index: I) -> Option<&Value> {
-        let value = self.yaml.map(|v| v.get(&index)).flatten();
-        value.or_else(|| self.default.get(&index))
+        let layers = [
+            self.yaml,
+            Some(self.default),
+            self.yaml_department,
+            self.default_department,
+        ];
+        layers.into_iter().flatten().find_map(|v| v.get(&index))
     }
 
+    // A department with `Enabled: false` disables its cops unless they are
+    // explicitly enabled, while `Enabled: true` doesn't turn on cops that are
+    // pending or disabled by default.
     pub fn is_cop_enabled(&self, cop: &dyn cop::Base) -> bool {
-        self.for_cop(cop)["Enabled"].as_bool().unwrap_or(false)
+        // Like its directives, the configuration can't disable `Lint/Syntax`.
+        if cop.name() == cop::lint::Syntax.name() {
+            return true;
+        }
+        let config = self.for_cop(cop);
+        if config.yaml.and_then(|v| v.get("Enabled")) == Some(&Value::Bool(true)) {
+            return true;
+        }
+        let departments = [config.yaml_department, config.default_department];
+        let department = departments.into_iter().flatten().find_map(|v| v.get("Enabled"));
+        if department == Some(&Value::Bool(false)) {
+            return false;
+        }
+
+        match &config["Enabled"] {
+            Value::Bool(enabled) => *enabled,
+            Value::String(s) if s == "pending" => self.for_all_cops()["NewCops"] == "enable",
+            _ => false,
+        }
+    }
+
+    pub fn is_cop_pending(&self, cop: &dyn cop::Base) -> bool {
+        self.for_cop(cop)["Enabled"] == "pending"
+    }
+
+    pub fn severity(&self, cop: &dyn cop::Base) -> cop::Severity {
+        let config = self.for_cop(cop);
+        let severity = config["Severity"]
+            .as_str()
+            .and_then(cop::Severity::from_name);
+        severity.unwrap_or_else(|| cop::Severity::default_for(cop.name()))
+    }
+
+    // Corrections of cops marked `Safe: false` or `SafeAutoCorrect: false` may
+    // change what the code does, so they are only applied with `-A`.
+    pub fn is_safe_autocorrect(&self, cop: &dyn cop::Base) -> bool {
+        let config = self.for_cop(cop);
+        let is_safe = |key| config[key].as_bool().unwrap_or(true);
+        is_safe("Safe") && is_safe("SafeAutoCorrect")
+    }
+
+    pub fn is_file_to_include(&self, path: &Path) -> bool {
+        self.match_any(&self.for_all_cops()["Include"], path)
+    }
+
+    pub fn is_file_to_exclude(&self, path: &Path) -> bool {
+        self.match_any(&self.for_all_cops()["Exclude"], path)
+    }
+
+    // Directories are skipped as a whole if excluded by a `dir/**/*` pattern.
+    pub fn is_dir_to_exclude(&self, path: &Path) -> bool {
+        let all_cops = self.for_all_cops();
+        let Some(patterns) = all_cops["Exclude"].as_sequence() else { return false };
+        let mut patterns = patterns
+            .iter()
+            .filter_map(|p| p.as_str()?.strip_suffix("/**/*"));
+        patterns.any(|p| self.match_pattern(p, path))
+    }
+
+    pub fn is_relevant_file(&self, cop: &dyn cop::Base, path: &Path) -> bool {
+        let config = self.for_cop(cop);
+        let included = match config.get("Include") {
+            Some(Value::Sequence(patterns)) if patterns.is_empty() => true,
+            Some(patterns) => self.match_any(patterns, path),
+            None => true,
+        };
+        included
+            && !config
+                .get("Exclude")
+                .is_some_and(|p| self.match_any(p, path))
+    }
+
+    fn match_any(&self, patterns: &Value, path: &Path) -> bool {
+        let Some(patterns) = patterns.as_sequence() else { return false };
+        let mut patterns = patterns.iter().filter_map(|p| p.as_str());
+        patterns.any(|p| self.match_pattern(p, path))
+    }
+
+    // Patterns from configuration files are absolute, while default patterns
+    // are relative to the base directory, so both forms of the path are tried.
+    fn match_pattern(&self, pattern: &str, path: &Path) -> bool {
+        let absolute = path_util::absolute_path(path);
+        let base_dir = path_util::absolute_path(self.base_dir.unwrap_or(Path::new("")));
+        let relative = absolute.strip_prefix(&base_dir).unwrap_or(&absolute);
+        let paths = [absolute.as_path(), relative];
+        paths
+            .iter()
+            .any(|p| path_util::match_path(pattern, &p.to_string_lossy()))
     }
 
     pub fn is_active_support_extensions_enabled(&self) -> bool {
@@ -50,10 +175,7 @@ impl Copy for Config<'_> {}
 
 impl Clone for Config<'_> {
     fn clone(&self) -> Self {
-        Self {
-            yaml: self.yaml,
-            default: self.default,
-        }
+        *self
     }
 }
 
@@ -61,7 +183,7 @@ impl<I: Index> std::ops::Index<I> for Config<'_> {
     type Output = Value;
 
     fn index(&self, index: I) -> &Value {
-        let value = self.yaml.map(|v| v.get(&index)).flatten();
+        let value = self.get(&index);
         value.unwrap_or_else(|| &self.default[&index])
     }
 }
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde_yaml::{Mapping, Value};
+
+use crate::path_util;
+
+pub const DOTFILE: &str = ".rubocop.yml";
+pub const AUTO_GENERATED_FILE: &str = ".rubocop_todo.yml";
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub struct ConfigStore {
+    options_config: Option<PathBuf>,
+    auto_gen_config: bool,
+    config_paths: Mutex<HashMap<PathBuf, Option<PathBuf>>>,
+    configs: Mutex<HashMap<PathBuf, Arc<Value>>>,
+}
+
+impl ConfigStore {
+    pub fn new(options_config: Option<PathBuf>) -> Self {
+        Self {
+            options_config,
+            auto_gen_config: false,
+            config_paths: Mutex::new(HashMap::new()),
+            configs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // When generating `.rubocop_todo.yml`, the previously generated file is
+    // not inherited so that all offenses are found again.
+    pub fn with_auto_gen_config(self, auto_gen_config: bool) -> Self {
+        Self {
+            auto_gen_config,
+            ..self
+        }
+    }
+
+    pub fn for_file(&self, path: &Path) -> Result<Option<Arc<Value>>, ConfigError> {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        self.for_dir(dir)
+    }
+
+    pub fn for_dir(&self, dir: &Path) -> Result<Option<Arc<Value>>, ConfigError> {
+        let path = match &self.options_config {
+            Some(path) => Some(path.clone()),
+            None => self.find_config_path(dir),
+        };
+        match path {
+            Some(path) => self.load(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // Relative paths in the configuration of a file are resolved against the
+    // returned directory.
+    pub fn base_dir_for_file(&self, path: &Path) -> PathBuf {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let path = match &self.options_config {
+            Some(path) => Some(path.clone()),
+            None => self.find_config_path(dir),
+        };
+        match path {
+            Some(path) => base_dir_for_path_parameters(&path),
+            None => path_util::absolute_path(Path::new("")),
+        }
+    }
+
+    fn find_config_path(&self, dir: &Path) -> Option<PathBuf> {
+        let dir = path_util::absolute_path(dir);
+
+        let mut config_paths = self.config_paths.lock().unwrap();
+        if let Some(path) = config_paths.get(&dir) {
+            return path.clone();
+        }
+
+        let path = dir
+            .ancestors()
+            .map(|d| d.join(DOTFILE))
+            .find(|p| p.is_file());
+        config_paths.insert(dir, path.clone());
+        path
+    }
+
+    fn load(&self, path: &Path) -> Result<Arc<Value>, ConfigError> {
+        if let Some(config) = self.configs.lock().unwrap().get(path) {
+            return Ok(config.clone());
+        }
+
+        let config = load_file(path, self.auto_gen_config, &mut Vec::new())?;
+        let mut config = merge_with_default(config, crate::default::config());
+        config.remove("inherit_mode");
+
+        let config = Arc::new(Value::Mapping(config));
+        let mut configs = self.configs.lock().unwrap();
+        configs.insert(path.to_path_buf(), config.clone());
+        Ok(config)
+    }
+}
+
+fn load_file(
+    path: &Path,
+    auto_gen_config: bool,
+    stack: &mut Vec<PathBuf>,
+) -> Result<Mapping, ConfigError> {
+    let error = |message: String| ConfigError {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    if stack.iter().any(|p| p == path) {
+        return Err(error("circular inherit_from detected".into()));
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| error(e.to_string()))?;
+    let mut config = match serde_yaml::from_slice(&bytes) {
+        Ok(Value::Null) => Mapping::new(),
+        Ok(Value::Mapping(config)) => config,
+        Ok(_) => return Err(error("configuration must be a mapping".into())),
+        Err(e) => return Err(error(e.to_string())),
+    };
+
+    let inherit_from = match config.remove("inherit_from") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::String(s)) => vec![s],
+        Some(Value::Sequence(seq)) => {
+            let paths = seq.into_iter().map(|v| match v {
+                Value::String(s) => Ok(s),
+                _ => Err(error("inherit_from must contain paths".into())),
+            });
+            paths.collect::<Result<_, _>>()?
+        }
+        Some(_) => return Err(error("inherit_from must be a list of paths".into())),
+    };
+
+    make_paths_absolute(&mut config, &base_dir_for_path_parameters(path));
+
+    stack.push(path.to_path_buf());
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let mut base = Mapping::new();
+    for inherited in inherit_from {
+        if auto_gen_config && inherited == AUTO_GENERATED_FILE {
+            continue;
+        }
+        let inherited = load_file(&dir.join(inherited), auto_gen_config, stack)?;
+        base = merge(base, inherited, None);
+    }
+    stack.pop();
+
+    let root_mode = config.get("inherit_mode").cloned();
+    Ok(merge(base, config, root_mode.as_ref()))
+}
+
+// Paths in `.rubocop*` files are relative to the directory of the file,
+// while those in other files are relative to the current directory.
+fn base_dir_for_path_parameters(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    match path.parent() {
+        Some(dir) if file_name.starts_with(".rubocop") => path_util::absolute_path(dir),
+        _ => path_util::absolute_path(Path::new("")),
+    }
+}
+
+fn make_paths_absolute(config: &mut Mapping, base_dir: &Path) {
+    for section in config.values_mut() {
+        let Value::Mapping(section) = section else { continue };
+        for key in ["Include", "Exclude"] {
+            let Some(Value::Sequence(patterns)) = section.get_mut(key) else { continue };
+            for pattern in patterns.iter_mut() {
+                let Value::String(pattern) = pattern else { continue };
+                let absolute = base_dir.join(&*pattern);
+                *pattern = absolute.to_string_lossy().into_owned();
+            }
+        }
+    }
+}
+
+// Settings of the derived configuration replace those of the base, except
+// for mappings, which are merged recursively, and lists named in a `merge`
+// inherit mode, which are concatenated.
+fn merge(base: Mapping, derived: Mapping, root_mode: Option<&Value>) -> Mapping {
+    let derived_mode = derived.get("inherit_mode").cloned();
+    let mut result = base;
+    for (key, value) in derived {
+        let value = match (result.remove(&key), value) {
+            (Some(Value::Mapping(base)), Value::Mapping(derived)) => {
+                Value::Mapping(merge(base, derived, root_mode))
+            }
+            (Some(Value::Sequence(base)), Value::Sequence(derived))
+                if should_union(derived_mode.as_ref(), root_mode, &key) =>
+            {
+                Value::Sequence(union(base, derived))
+            }
+            (_, value) => value,
+        };
+        result.insert(key, value);
+    }
+    result
+}
+
+fn merge_with_default(mut config: Mapping, default: &Value) -> Mapping {
+    let root_mode = config.get("inherit_mode").cloned();
+    for (name, section) in config.iter_mut() {
+        let Value::Mapping(section) = section else { continue };
+        let Some(Value::Mapping(default)) = default.get(name) else { continue };
+        let mode = section.get("inherit_mode").cloned();
+        for (key, value) in section.iter_mut() {
+            let Value::Sequence(derived) = value else { continue };
+            let Some(Value::Sequence(base)) = default.get(key) else { continue };
+            if should_union(mode.as_ref(), root_mode.as_ref(), key) {
+                *derived = union(base.clone(), std::mem::take(derived));
+            }
+        }
+    }
+    config
+}
+
+fn should_union(derived_mode: Option<&Value>, root_mode: Option<&Value>, key: &Value) -> bool {
+    if mode_includes(derived_mode, "override", key) {
+        return false;
+    }
+    mode_includes(derived_mode, "merge", key) || mode_includes(root_mode, "merge", key)
+}
+
+fn mode_includes(mode: Option<&Value>, kind: &str, key: &Value) -> bool {
+    let keys = mode.and_then(|m| m.get(kind)).and_then(|k| k.as_sequence());
+    keys.is_some_and(|keys| keys.contains(key))
+}
+
+fn union(mut base: Vec<Value>, derived: Vec<Value>) -> Vec<Value> {
+    for value in derived {
+        if !base.contains(&value) {
+            base.push(value);
+        }
+    }
+    base
+}
@@ -1,17 +1,19 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use lib_ruby_parser::Loc;
+use lib_ruby_parser::{Diagnostic, Loc};
 
 use crate::ast::NodeRef;
 use crate::cop::mixin::range_help::*;
-use crate::cop::{self, Offense};
+use crate::cop::{self, Name, Offense};
 use crate::source::DecodedInput;
-use crate::Config;
+use crate::{CommentConfig, Config};
 
 pub struct Context<'cfg, 'ast> {
     config: Config<'cfg>,
     input: DecodedInput,
+    comment_config: CommentConfig,
+    diagnostics: Vec<Diagnostic>,
     parents: HashMap<NodeRef<'ast>, NodeRef<'ast>>,
     offenses: Vec<Offense>,
 }
@@ -20,11 +22,15 @@ impl<'cfg, 'ast> Context<'cfg, 'ast> {
     pub fn new(
         config: Config<'cfg>,
         input: DecodedInput,
+        comment_config: CommentConfig,
+        diagnostics: Vec<Diagnostic>,
         parents: HashMap<NodeRef<'ast>, NodeRef<'ast>>,
     ) -> Self {
         Self {
             config,
             input,
+            comment_config,
+            diagnostics,
             parents,
             offenses: Vec::new(),
         }
@@ -34,15 +40,55 @@ impl<'cfg, 'ast> Context<'cfg, 'ast> {
         (self.input, self.offenses)
     }
 
+    pub fn input(&self) -> &DecodedInput {
+        &self.input
+    }
+
+    pub fn cop_config(&self, cop: &dyn cop::Base) -> Config<'cfg> {
+        self.config.for_cop(cop)
+    }
+
+    pub fn comment_config(&self) -> &CommentConfig {
+        &self.comment_config
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn offenses(&self) -> &[Offense] {
+        &self.offenses
+    }
+
+    pub fn severity(&self, cop: &dyn cop::Base) -> cop::Severity {
+        self.config.severity(cop)
+    }
+
     pub fn is_cop_enabled(&self, cop: &dyn cop::Base) -> bool {
         self.config.is_cop_enabled(cop)
     }
 
+    pub fn is_safe_autocorrect(&self, cop: &dyn cop::Base) -> bool {
+        self.config.is_safe_autocorrect(cop)
+    }
+
     pub fn is_active_support_extensions_enabled(&self) -> bool {
         self.config.is_active_support_extensions_enabled()
     }
 
-    pub fn add_offense(&mut self, offense: Offense) {
+    // Syntax errors can't be disabled, so that files that can't be parsed are
+    // never reported as clean.
+    pub fn is_offense_disabled(&self, offense: &Offense) -> bool {
+        if offense.cop_name == cop::lint::Syntax.name() {
+            return false;
+        }
+        let (line, _) = self.line_col_for_pos(offense.loc.begin);
+        let config = &self.comment_config;
+        config.is_cop_disabled_line(offense.cop_name, line)
+    }
+
+    pub fn add_offense(&mut self, mut offense: Offense) {
+        offense.disabled = self.is_offense_disabled(&offense);
         self.offenses.push(offense)
     }
 
@@ -57,7 +103,7 @@ impl<'cfg, 'ast> Context<'cfg, 'ast> {
         self.input.line_col_for_pos(pos)
     }
 
-    pub fn source(&self, loc: Loc) -> Cow<str> {
+    pub fn source(&self, loc: Loc) -> Cow<'_, str> {
         self.input.source(loc)
     }
 
@@ -71,11 +117,11 @@ impl RangeHelp for Context<'_, '_> {
         self.input.range_with_surrounding_comma(range, side)
     }
 
-    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder {
+    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder<'_> {
         self.input.range_with_surrounding_space(range)
     }
 
-    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder {
+    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder<'_> {
         self.input.range_by_whole_lines(range)
     }
 }
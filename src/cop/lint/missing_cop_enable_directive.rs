@@ -0,0 +1,42 @@
+use crate::cop::*;
+
+pub struct MissingCopEnableDirective;
+
+impl Base for MissingCopEnableDirective {
+    fn on_new_investigation(&self, ctx: &mut Context, _corrector: &mut Corrector) {
+        let max_range = &ctx.cop_config(self)["MaximumRangeSize"];
+        let max_range = max_range.as_f64().unwrap_or(f64::INFINITY);
+
+        let mut offenses = Vec::new();
+        let comment_config = ctx.comment_config();
+        for range in comment_config.disabled_ranges.iter() {
+            let size = match range.end_line {
+                Some(end_line) => (end_line - range.begin_line) as f64,
+                None => f64::INFINITY,
+            };
+            // This has to remain a strict inequality to handle the case when
+            // `max_range` is infinite.
+            if size < max_range + 2.0 {
+                continue;
+            }
+
+            let name = match range.name.as_str() {
+                "all" => "all cops".to_string(),
+                name if name.contains('/') => format!("{name} cop"),
+                name => format!("{name} department"),
+            };
+            let message = if max_range.is_infinite() {
+                format!("Re-enable {name} with `# rubocop:enable` after disabling it.")
+            } else {
+                format!("Re-enable {name} within {max_range} lines after disabling it.")
+            };
+
+            let directive = &comment_config.directives[range.directive];
+            offenses.push((directive.loc, message));
+        }
+
+        for (loc, message) in offenses {
+            add_offense!(self, ctx, loc, message);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::cop::mixin::range_help::*;
+use crate::cop::*;
+use crate::{default, DirectiveComment};
+
+#[derive(AutoCorrector)]
+pub struct RedundantCopDisableDirective;
+
+impl Base for RedundantCopDisableDirective {
+    fn on_investigation_end(&self, ctx: &mut Context, corrector: &mut Corrector) {
+        let comment_config = ctx.comment_config();
+
+        let mut offenses = Vec::new();
+        for (index, directive) in comment_config.directives.iter().enumerate() {
+            if !directive.is_disabling() {
+                continue;
+            }
+
+            let redundant = directive.cop_names.iter().map(|name| {
+                if name == self.name() {
+                    return false;
+                }
+                let ranges = comment_config.disabled_ranges.iter();
+                let mut ranges = ranges.filter(|r| r.directive == index && r.name == *name);
+                // A name that is already disabled doesn't start a new range.
+                let Some(range) = ranges.next() else { return true };
+                !ctx.offenses().iter().any(|offense| {
+                    let (line, _) = ctx.line_col_for_pos(offense.loc.begin);
+                    range.disables(offense.cop_name, line)
+                })
+            });
+            let redundant = redundant.collect::<Vec<_>>();
+
+            if redundant.iter().all(|&r| r) {
+                let names = directive.cop_names.iter().map(|name| describe(name));
+                let message = names.collect::<Vec<_>>().join(", ");
+                let message = format!("Unnecessary disabling of {message}.");
+                let range = ctx
+                    .range_with_surrounding_space(directive.loc)
+                    .side(Side::Left)
+                    .build();
+                let range = ctx
+                    .range_with_surrounding_space(range)
+                    .side(Side::Right)
+                    .newlines(false)
+                    .build();
+                offenses.push((directive.loc, message, range));
+                continue;
+            }
+
+            let locs = cop_name_locs(&ctx.source(directive.loc), directive);
+            for (i, loc) in locs.into_iter().enumerate() {
+                if !redundant[i] {
+                    continue;
+                }
+                let name = describe(&directive.cop_names[i]);
+                let message = format!("Unnecessary disabling of {name}.");
+                // Names followed only by redundant names are removed along
+                // with the preceding comma, others with the following one.
+                let range = if redundant[i..].iter().all(|&r| r) {
+                    let range = ctx
+                        .range_with_surrounding_space(loc)
+                        .side(Side::Left)
+                        .build();
+                    ctx.range_with_surrounding_comma(range, Side::Left)
+                } else {
+                    let range = ctx.range_with_surrounding_comma(loc, Side::Right);
+                    ctx.range_with_surrounding_space(range)
+                        .side(Side::Right)
+                        .build()
+                };
+                offenses.push((loc, message, range));
+            }
+        }
+
+        for (loc, message, range) in offenses {
+            add_offense!(self, ctx, corrector, loc, message, {
+                corrector.remove(range);
+            });
+        }
+    }
+}
+
+fn describe(name: &str) -> String {
+    let cops = default::cops();
+    if name == "all" {
+        "all cops".to_string()
+    } else if cops.iter().any(|cop| cop.name() == name) {
+        format!("`{name}`")
+    } else if cops.iter().any(|cop| cop.name().starts_with(&format!("{name}/"))) {
+        format!("`{name}` department")
+    } else {
+        format!("`{name}` (unknown cop)")
+    }
+}
+
+fn cop_name_locs(text: &str, directive: &DirectiveComment) -> Vec<Loc> {
+    let mut offset = text.find(':').map_or(0, |pos| pos + 1);
+    let mut locs = Vec::new();
+    for name in directive.cop_names.iter() {
+        let pos = offset + text[offset..].find(name.as_str()).unwrap();
+        offset = pos + name.len();
+        locs.push(Loc {
+            begin: directive.loc.begin + pos,
+            end: directive.loc.begin + offset,
+        });
+    }
+    locs
+}
@@ -0,0 +1,34 @@
+use crate::cop::*;
+
+pub struct Syntax;
+
+impl Base for Syntax {
+    fn on_new_investigation(&self, ctx: &mut Context, _corrector: &mut Corrector) {
+        let config = ctx.cop_config(self);
+        let include_warnings = config["IncludeWarnings"].as_bool().unwrap_or(false);
+        let custom_severity = config["Severity"].as_str().and_then(Severity::from_name);
+
+        let diagnostics = ctx.diagnostics().iter();
+        let diagnostics = diagnostics.filter(|d| d.is_error() || include_warnings);
+        let diagnostics = diagnostics.map(|d| (d.loc, d.render_message(), d.is_error()));
+        for (loc, message, is_error) in diagnostics.collect::<Vec<_>>() {
+            // Syntax errors are fatal unless configured otherwise.
+            let severity = match (custom_severity, is_error) {
+                (Some(severity), _) => severity,
+                (None, true) => Severity::Fatal,
+                (None, false) => Severity::Warning,
+            };
+            let loc = ctx.intersect(loc);
+            ctx.add_offense(Offense {
+                loc,
+                correctable: false,
+                corrected: false,
+                disabled: false,
+                severity,
+                cop_name: self.name(),
+                message: Cow::from(message),
+                edits: Vec::new(),
+            });
+        }
+    }
+}
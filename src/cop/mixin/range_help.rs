@@ -4,8 +4,8 @@ use crate::source::DecodedInput;
 
 pub trait RangeHelp {
     fn range_with_surrounding_comma(&self, range: Loc, side: Side) -> Loc;
-    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder;
-    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder;
+    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder<'_>;
+    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder<'_>;
 }
 
 impl RangeHelp for DecodedInput {
@@ -13,11 +13,11 @@ impl RangeHelp for DecodedInput {
         range_with_surrounding_comma(self, range, side)
     }
 
-    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder {
+    fn range_with_surrounding_space(&self, range: Loc) -> WithSurroundingSpaceBuilder<'_> {
         WithSurroundingSpaceBuilder::new(self, range)
     }
 
-    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder {
+    fn range_by_whole_lines(&self, range: Loc) -> ByWholeLinesBuilder<'_> {
         ByWholeLinesBuilder::new(self, range)
     }
 }
@@ -184,9 +184,9 @@ where
     F: Fn(u8) -> bool,
 {
     let offset: isize = if step == -1 { -1 } else { 0 };
-    while {
-        let i = pos.checked_add_signed(offset).unwrap();
-        condition && f(src.bytes[i])
+    while condition && {
+        let i = pos.checked_add_signed(offset);
+        i.and_then(|i| src.bytes.get(i)).is_some_and(|&b| f(b))
     } {
         pos = pos.saturating_add_signed(step);
     }
@@ -202,9 +202,9 @@ fn move_pos_str(
 ) -> usize {
     let size = needle.len();
     let offset: isize = if step == -1 { -1 } else { 0 };
-    while {
-        let i = pos.checked_add_signed(offset).unwrap();
-        condition && &src.bytes[i..i + size] == needle
+    while condition && {
+        let i = pos.checked_add_signed(offset);
+        i.and_then(|i| src.bytes.get(i..i + size)) == Some(needle)
     } {
         for _ in 0..size {
             pos = pos.saturating_add_signed(step);
@@ -9,7 +9,11 @@ pub mod mixin;
 mod name;
 pub use name::*;
 
+mod severity;
+pub use severity::*;
+
 pub mod layout;
+pub mod lint;
 pub mod style;
 
 use std::borrow::Cow;
@@ -36,8 +40,14 @@ impl std::fmt::Display for dyn Base {
 pub struct Offense {
     pub loc: Loc,
     pub correctable: bool,
+    pub corrected: bool,
+    pub disabled: bool,
+    pub severity: Severity,
     pub cop_name: &'static str,
     pub message: Cow<'static, str>,
+    // Corrections of this offense alone, as ranges of the inspected source and
+    // their replacements.
+    pub edits: Vec<(Loc, String)>,
 }
 
 #[macro_export]
@@ -46,20 +56,43 @@ macro_rules! add_offense {
         $ctx.add_offense(Offense {
             loc: $loc,
             correctable: false,
+            corrected: false,
+            disabled: false,
+            severity: $ctx.severity($cop),
             cop_name: $cop.name(),
             message: Cow::from($message),
+            edits: Vec::new(),
         });
     };
 
-    ($cop:expr, $ctx:expr, $loc:expr, $message:expr, $autocorrect:block) => {{
+    // The autocorrect block writes to a corrector of its own, which is then
+    // merged into `$corrector`.
+    ($cop:expr, $ctx:expr, $corrector:ident, $loc:expr, $message:expr, $autocorrect:block) => {{
         let _: &dyn AutoCorrector = $cop;
-        let offense = Offense {
+        let mut offense = Offense {
             loc: $loc,
             correctable: true,
+            corrected: false,
+            disabled: false,
+            severity: $ctx.severity($cop),
             cop_name: $cop.name(),
             message: Cow::from($message),
+            edits: Vec::new(),
         };
-        $autocorrect;
+        if !$ctx.is_offense_disabled(&offense) {
+            let mut offense_corrector =
+                $crate::source::Corrector::new($ctx.input().as_shared_bytes());
+            {
+                let $corrector = &mut offense_corrector;
+                $autocorrect;
+            }
+            // Corrections that clash with those of a previous offense are
+            // left for the next pass of the autocorrect loop, but still
+            // reported with the offense.
+            offense.edits = offense_corrector.edits();
+            let merged = $corrector.merge(offense_corrector).is_ok();
+            offense.corrected = merged && !offense.edits.is_empty();
+        }
         $ctx.add_offense(offense);
     }};
 }
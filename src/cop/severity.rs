@@ -0,0 +1,69 @@
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Refactor,
+    Convention,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl Severity {
+    const ALL: [Severity; 6] = [
+        Severity::Info,
+        Severity::Refactor,
+        Severity::Convention,
+        Severity::Warning,
+        Severity::Error,
+        Severity::Fatal,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Refactor => "refactor",
+            Severity::Convention => "convention",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        }
+    }
+
+    pub fn code(self) -> char {
+        match self {
+            Severity::Info => 'I',
+            Severity::Refactor => 'R',
+            Severity::Convention => 'C',
+            Severity::Warning => 'W',
+            Severity::Error => 'E',
+            Severity::Fatal => 'F',
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Severity> {
+        Self::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    pub fn from_code(code: &str) -> Option<Severity> {
+        let mut chars = code.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else { return None };
+        Self::ALL
+            .into_iter()
+            .find(|s| s.code() == c.to_ascii_uppercase())
+    }
+
+    // Lint cops report warnings, while all other departments report
+    // conventions unless configured otherwise.
+    pub fn default_for(cop_name: &str) -> Severity {
+        match cop_name.split_once('/') {
+            Some(("Lint", _)) => Severity::Warning,
+            _ => Severity::Convention,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
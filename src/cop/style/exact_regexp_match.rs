@@ -28,7 +28,7 @@ impl Base for ExactRegexpMatch {
             format!("{receiver} {new_method} '{literal}'")
         };
 
-        add_offense!(self, ctx, node.expression_l, msg(&prefer), {
+        add_offense!(self, ctx, corrector, node.expression_l, msg(&prefer), {
             corrector.replace(node.expression_l, prefer);
         });
     }
@@ -14,7 +14,7 @@ impl Base for MethodDefParentheses {
             return;
         }
 
-        add_offense!(self, ctx, args.expression_l, MSG, {
+        add_offense!(self, ctx, corrector, args.expression_l, MSG, {
             let begin_l = Loc {
                 begin: node.name_l.end,
                 end: args.expression_l.begin,
@@ -23,7 +23,7 @@ impl Base for Not {
     fn on_send(&self, ctx: &mut Context, corrector: &mut Corrector, node: &Send) {
         let Some((receiver, selector)) = prefix_not(ctx, node) else { return };
 
-        add_offense!(self, ctx, selector, MSG, {
+        add_offense!(self, ctx, corrector, selector, MSG, {
             let range = ctx
                 .range_with_surrounding_space(selector)
                 .side(Side::Right)
@@ -87,7 +87,7 @@ node_matcher!(
 );
 
 fn prefix_not<'ast>(ctx: &Context, node: &'ast Send) -> Option<(&'ast Node, Loc)> {
-    let Some((receiver, selector)) = negation_method(node) else { return None };
+    let (receiver, selector) = negation_method(node)?;
     let selector = *selector;
     if ctx.source(selector) == "not" {
         Some((receiver, selector))
@@ -42,7 +42,7 @@ impl RedundantArrayConstructor {
         expression: Loc,
         replacement: Loc,
     ) {
-        add_offense!(self, ctx, range, MSG, {
+        add_offense!(self, ctx, corrector, range, MSG, {
             corrector.replace(expression, ctx.source(replacement));
         });
     }
@@ -41,7 +41,7 @@ impl Base for RedundantFilterChain {
         let replacement = replacement_methods()[second_method];
         let message = msg(replacement, first_method, second_method);
 
-        add_offense!(self, ctx, range, message, {
+        add_offense!(self, ctx, corrector, range, message, {
             corrector.remove(Loc {
                 begin: receiver.expression().end,
                 end: predicate_selector.end,
@@ -11,7 +11,7 @@ impl Base for RedundantRegexpConstructor {
     fn on_send(&self, ctx: &mut Context, corrector: &mut Corrector, node: &Send) {
         let Some(regexp) = redundant_regexp_constructor(node) else { return };
 
-        add_offense!(self, ctx, node.expression_l, msg(&node.method_name), {
+        add_offense!(self, ctx, corrector, node.expression_l, msg(&node.method_name), {
             corrector.replace(node.expression_l, ctx.source(regexp.expression_l));
         });
     }
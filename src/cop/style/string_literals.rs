@@ -15,7 +15,7 @@ impl Base for StringLiterals {
             return;
         }
 
-        add_offense!(self, ctx, node.expression_l, MSG, {
+        add_offense!(self, ctx, corrector, node.expression_l, MSG, {
             corrector.replace(begin_l, '\'');
             corrector.replace(end_l, '\'');
         });
@@ -0,0 +1,163 @@
+use std::io::{Result, Write};
+
+// Lines of unchanged source shown around each change.
+const CONTEXT: usize = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Equal,
+    Delete,
+    Insert,
+}
+
+// Writes the changes between two versions of a file in the unified format
+// of `diff -u`, or nothing if they are the same.
+pub fn write_unified_diff(
+    out: &mut dyn Write,
+    name: &str,
+    original: &[u8],
+    corrected: &[u8],
+) -> Result<()> {
+    let a = original
+        .split_inclusive(|&b| b == b'\n')
+        .collect::<Vec<_>>();
+    let b = corrected
+        .split_inclusive(|&b| b == b'\n')
+        .collect::<Vec<_>>();
+    let script = edit_script(&a, &b);
+
+    // Positions in both files before each edit.
+    let mut positions = Vec::with_capacity(script.len() + 1);
+    let (mut i, mut j) = (0, 0);
+    for edit in &script {
+        positions.push((i, j));
+        match edit {
+            Edit::Equal => (i, j) = (i + 1, j + 1),
+            Edit::Delete => i += 1,
+            Edit::Insert => j += 1,
+        }
+    }
+    positions.push((i, j));
+
+    let changes = script
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| **e != Edit::Equal);
+    let changes = changes.map(|(index, _)| index).collect::<Vec<_>>();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "--- {name}")?;
+    writeln!(out, "+++ {name}")?;
+
+    // Changes at most twice the context apart share a hunk.
+    let mut hunk_start = 0;
+    for (n, &change) in changes.iter().enumerate() {
+        let next = changes.get(n + 1);
+        if next.is_some_and(|&next| next - change - 1 <= 2 * CONTEXT) {
+            continue;
+        }
+
+        let begin = changes[hunk_start].saturating_sub(CONTEXT);
+        let end = (change + 1 + CONTEXT).min(script.len());
+        let (a_begin, b_begin) = positions[begin];
+        let (a_end, b_end) = positions[end];
+        writeln!(
+            out,
+            "@@ -{} +{} @@",
+            hunk_range(a_begin, a_end - a_begin),
+            hunk_range(b_begin, b_end - b_begin)
+        )?;
+
+        for index in begin..end {
+            let (i, j) = positions[index];
+            let (prefix, line) = match script[index] {
+                Edit::Equal => (b' ', a[i]),
+                Edit::Delete => (b'-', a[i]),
+                Edit::Insert => (b'+', b[j]),
+            };
+            out.write_all(&[prefix])?;
+            out.write_all(line)?;
+            if !line.ends_with(b"\n") {
+                out.write_all(b"\n\\ No newline at end of file\n")?;
+            }
+        }
+
+        hunk_start = n + 1;
+    }
+    Ok(())
+}
+
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{count}", start + 1),
+    }
+}
+
+// Myers' algorithm: finds a shortest sequence of edits turning `a` into `b`.
+// The furthest reaching paths of each step are kept to walk back from the
+// end of both files.
+fn edit_script(a: &[&[u8]], b: &[&[u8]]) -> Vec<Edit> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m + 1;
+    let mut v = vec![0; 2 * offset as usize + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=n + m {
+        let mut done = false;
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                done = true;
+                break;
+            }
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        if done {
+            break;
+        }
+    }
+
+    let mut script = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[d as usize - 1];
+        let furthest = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest(previous_k);
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            script.push(Edit::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        script.push(if x == previous_x {
+            Edit::Insert
+        } else {
+            Edit::Delete
+        });
+        (x, y) = (previous_x, previous_y);
+    }
+    script.extend((0..x).map(|_| Edit::Equal));
+    script.reverse();
+    script
+}
@@ -0,0 +1,25 @@
+use crate::cop::Offense;
+use crate::source::DecodedInput;
+use crate::{Error, Options};
+
+// 0 if no offenses at or above the fail level were found, 1 if some were,
+// and 2 if an error occurred. With `--diff`, it is 1 if any file would be
+// changed instead.
+pub fn exit_status(
+    options: &Options,
+    files: &[(DecodedInput, Vec<Offense>)],
+    outputs: &[Option<Vec<u8>>],
+    errors: &[Error],
+) -> i32 {
+    if !errors.is_empty() {
+        return 2;
+    }
+
+    if options.diff {
+        return outputs.iter().any(Option::is_some) as i32;
+    }
+
+    let fail_level = options.fail_level();
+    let mut offenses = files.iter().flat_map(|(_, offenses)| offenses);
+    offenses.any(|o| !o.disabled && !o.corrected && o.severity >= fail_level) as i32
+}
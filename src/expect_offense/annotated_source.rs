@@ -101,7 +101,7 @@ impl AnnotatedSource {
 
 impl PartialEq for AnnotatedSource {
     fn eq(&self, other: &AnnotatedSource) -> bool {
-        self.lines == other.lines && self.matches_annotations(&other)
+        self.lines == other.lines && self.matches_annotations(other)
     }
 }
 
@@ -7,9 +7,9 @@ use lib_ruby_parser::{Parser, ParserOptions};
 
 use crate::cop::{self, Offense};
 use crate::source::{Corrector, DecodedInput};
-use crate::{Commissioner, Config};
+use crate::{Autocorrect, Commissioner, Config};
 
-pub fn normalize_source(source: &str) -> Cow<str> {
+pub fn normalize_source(source: &str) -> Cow<'_, str> {
     let lines = source.lines().collect::<Vec<_>>();
     match lines.as_slice() {
         [first, lines @ .., last] if first.is_empty() && last.trim().is_empty() => {
@@ -66,6 +66,10 @@ pub fn expect_correction(input: DecodedInput, corrector: Corrector, correction:
     assert_eq!(new_source, correction);
 }
 
+pub fn expect_no_corrections(corrector: Corrector) {
+    assert!(corrector.is_empty(), "Expected no corrections");
+}
+
 pub fn expect_no_offenses(
     config: Option<&serde_yaml::Mapping>,
     cop: &dyn cop::Base,
@@ -118,11 +122,33 @@ fn investigate(
         ..Default::default()
     };
     let parser = Parser::new(source, parser_options);
-    Commissioner::investigate(&[cop], config, parser)
+    Commissioner::investigate(&[cop], config, Autocorrect::All, parser)
 }
 
 #[macro_export]
 macro_rules! expect_offense {
+    (
+        cop = $cop:expr;
+        source = $source:expr;
+    ) => {
+        expect_offense! {
+            config = None;
+            cop = $cop;
+            source = $source;
+        }
+    };
+
+    (
+        config = $config:expr;
+        cop = $cop:expr;
+        source = $source:expr;
+    ) => {
+        let config = Option::from($config);
+        use $crate::expect_offense::*;
+        let (_, corrector) = expect_offense(config, $cop, &normalize_source($source), &[]);
+        expect_no_corrections(corrector);
+    };
+
     (
         cop = $cop:expr;
         source = $source:expr;
@@ -0,0 +1,36 @@
+use std::io::{Result, Write};
+
+use super::*;
+
+// Checkstyle XML report, as understood by most CI servers.
+pub struct CheckstyleFormatter;
+
+impl Formatter for CheckstyleFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        writeln!(out, "<?xml version='1.0'?>")?;
+        writeln!(out, "<checkstyle>")?;
+
+        for (input, offenses) in files {
+            writeln!(out, "  <file name='{}'>", xml_escape(&input.name))?;
+            for offense in reported_offenses(offenses) {
+                let (line, col) = input.line_col_for_pos(offense.loc.begin);
+                let message = xml_escape(&offense.message);
+                let source = xml_escape(offense.cop_name);
+                let severity = match offense.severity {
+                    Severity::Info | Severity::Refactor | Severity::Convention => "info",
+                    Severity::Warning => "warning",
+                    Severity::Error | Severity::Fatal => "error",
+                };
+                writeln!(
+                    out,
+                    "    <error line='{}' column='{}' severity='{severity}' message='{message}' source='{source}'/>",
+                    line + 1,
+                    col + 1
+                )?;
+            }
+            writeln!(out, "  </file>")?;
+        }
+
+        writeln!(out, "</checkstyle>")
+    }
+}
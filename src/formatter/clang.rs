@@ -0,0 +1,172 @@
+use std::io::{Result, Write};
+
+use lib_ruby_parser::Loc;
+
+use super::*;
+
+pub struct ClangStyleFormatter {
+    no_color: bool,
+}
+
+impl ClangStyleFormatter {
+    pub fn new(no_color: bool) -> ClangStyleFormatter {
+        ClangStyleFormatter { no_color }
+    }
+
+    fn set_color(&self, out: &mut dyn Write, color: Color) -> Result<()> {
+        if !self.no_color {
+            write!(out, "\x1b[{}m", color.fg_code())?;
+        }
+        Ok(())
+    }
+
+    fn reset_color(&self, out: &mut dyn Write) -> Result<()> {
+        if !self.no_color {
+            write!(out, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+
+    fn print_offense(
+        &self,
+        out: &mut dyn Write,
+        input: &DecodedInput,
+        offense: &Offense,
+    ) -> Result<()> {
+        let Offense {
+            loc,
+            cop_name,
+            message,
+            correctable,
+            corrected,
+            severity,
+            ..
+        } = offense;
+
+        let (begin_line, begin_col) = input.line_col_for_pos(loc.begin);
+        let (end_line, end_col) = input.line_col_for_pos(loc.end);
+
+        self.set_color(out, Color::Cyan)?;
+        write!(out, "{}", input.name)?;
+        self.reset_color(out)?;
+        write!(out, ":{}:{}: ", begin_line + 1, begin_col + 1)?;
+
+        self.set_color(out, severity_color(*severity))?;
+        write!(out, "{}", severity.code())?;
+        self.reset_color(out)?;
+        write!(out, ": ")?;
+
+        if *corrected {
+            self.set_color(out, Color::Green)?;
+            write!(out, "[Corrected]")?;
+            self.reset_color(out)?;
+            write!(out, " ")?;
+        } else if *correctable {
+            self.set_color(out, Color::Yellow)?;
+            write!(out, "[Correctable]")?;
+            self.reset_color(out)?;
+            write!(out, " ")?;
+        }
+
+        write!(out, "{cop_name}: ")?;
+
+        if self.no_color {
+            writeln!(out, "{message}")?;
+        } else {
+            let mut remaining: &str = message;
+            while let Some(index) = remaining.find('`') {
+                write!(out, "{}", &remaining[0..index])?;
+                remaining = &remaining[index + 1..];
+                let Some(index) = remaining.find('`') else {
+                    write!(out, "`")?;
+                    break;
+                };
+                self.set_color(out, Color::Yellow)?;
+                write!(out, "{}", &remaining[0..index])?;
+                self.reset_color(out)?;
+                remaining = &remaining[index + 1..];
+            }
+            writeln!(out, "{remaining}")?;
+        }
+
+        let line = &input.lines[begin_line];
+        let source = input.source(Loc {
+            begin: line.start,
+            end: line.end,
+        });
+        let source = source.trim_end_matches(['\n', '\r']);
+        if source.trim().is_empty() {
+            return Ok(());
+        }
+
+        // Offenses spanning multiple lines are highlighted up to the end of
+        // their first line.
+        write!(out, "{source}")?;
+        let end_col = if begin_line == end_line {
+            end_col
+        } else {
+            write!(out, " ")?;
+            self.set_color(out, Color::Yellow)?;
+            write!(out, "...")?;
+            self.reset_color(out)?;
+            source.len()
+        };
+        writeln!(out)?;
+
+        for _ in 0..begin_col {
+            write!(out, " ")?;
+        }
+        for _ in begin_col..end_col {
+            write!(out, "^")?;
+        }
+        writeln!(out)
+    }
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::BrightBlack,
+        Severity::Refactor | Severity::Convention => Color::Yellow,
+        Severity::Warning => Color::Magenta,
+        Severity::Error | Severity::Fatal => Color::Red,
+    }
+}
+
+impl Formatter for ClangStyleFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        let summary = Summary::new(files);
+
+        for (input, offenses) in files {
+            for offense in reported_offenses(offenses) {
+                self.print_offense(out, input, offense)?;
+            }
+        }
+
+        writeln!(out)?;
+
+        write!(out, "{} file(s) inspected, ", summary.file_count)?;
+
+        self.set_color(out, Color::Red)?;
+        write!(out, "{} offense(s)", summary.offense_count)?;
+        self.reset_color(out)?;
+        write!(out, " detected")?;
+
+        if summary.corrected_count > 0 {
+            write!(out, ", ")?;
+            self.set_color(out, Color::Green)?;
+            write!(out, "{} offense(s)", summary.corrected_count)?;
+            self.reset_color(out)?;
+            write!(out, " corrected")?;
+        }
+
+        if summary.correctable_count > 0 {
+            write!(out, ", ")?;
+            self.set_color(out, Color::Yellow)?;
+            write!(out, "{} offense(s)", summary.correctable_count)?;
+            self.reset_color(out)?;
+            write!(out, " autocorrectable")?;
+        }
+
+        writeln!(out)
+    }
+}
@@ -0,0 +1,48 @@
+use std::io::{Result, Write};
+
+use super::*;
+
+// GitHub Actions workflow commands, which show up as annotations on the diff.
+pub struct GitHubActionsFormatter {
+    fail_level: Severity,
+}
+
+impl GitHubActionsFormatter {
+    pub fn new(fail_level: Severity) -> GitHubActionsFormatter {
+        GitHubActionsFormatter { fail_level }
+    }
+
+    fn escape(s: &str) -> String {
+        s.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    }
+
+    fn escape_property(s: &str) -> String {
+        Self::escape(s).replace(':', "%3A").replace(',', "%2C")
+    }
+}
+
+impl Formatter for GitHubActionsFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        for (input, offenses) in files {
+            let file = Self::escape_property(&input.name);
+            for offense in reported_offenses(offenses) {
+                let (line, col) = input.line_col_for_pos(offense.loc.begin);
+                let message = Self::escape(&format!("{}: {}", offense.cop_name, offense.message));
+                let level = if offense.severity < self.fail_level {
+                    "warning"
+                } else {
+                    "error"
+                };
+                writeln!(
+                    out,
+                    "::{level} file={file},line={},col={}::{message}",
+                    line + 1,
+                    col + 1
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use std::io::{Result, Write};
+
+use serde_json::{json, Value};
+
+use super::*;
+
+// Ruby version of the grammar used by the parser.
+const RUBY_VERSION: &str = "3.1.1";
+
+// Output format of `rubocop --format json`.
+pub struct JsonFormatter;
+
+impl JsonFormatter {
+    fn offense_json(&self, input: &DecodedInput, offense: &Offense) -> Value {
+        let corrections = offense.edits.iter().map(|(loc, replacement)| {
+            json!({
+                "location": self.location_json(input, *loc),
+                "replacement": replacement,
+            })
+        });
+        json!({
+            "severity": offense.severity.name(),
+            "message": offense.message,
+            "cop_name": offense.cop_name,
+            "corrected": offense.corrected,
+            "correctable": offense.correctable,
+            "location": self.location_json(input, offense.loc),
+            "corrections": corrections.collect::<Vec<_>>(),
+        })
+    }
+
+    fn location_json(&self, input: &DecodedInput, loc: Loc) -> Value {
+        let (start_line, start_column) = input.line_col_for_pos(loc.begin);
+        let (last_line, last_column) = input.line_col_for_pos(loc.end);
+        json!({
+            "start_line": start_line + 1,
+            "start_column": start_column + 1,
+            "last_line": last_line + 1,
+            "last_column": last_column,
+            "length": input.source(loc).chars().count(),
+            "line": start_line + 1,
+            "column": start_column + 1,
+        })
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        let summary = Summary::new(files);
+
+        let files = files.iter().map(|(input, offenses)| {
+            let offenses = reported_offenses(offenses).into_iter();
+            let offenses = offenses.map(|o| self.offense_json(input, o));
+            json!({
+                "path": input.name,
+                "offenses": offenses.collect::<Vec<_>>(),
+            })
+        });
+
+        let output = json!({
+            "metadata": {
+                "rubocop_version": env!("CARGO_PKG_VERSION"),
+                "ruby_engine": "ruby",
+                "ruby_version": RUBY_VERSION,
+                "ruby_platform": format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+            },
+            "files": files.collect::<Vec<_>>(),
+            "summary": {
+                "offense_count": summary.offense_count,
+                "target_file_count": summary.file_count,
+                "inspected_file_count": summary.file_count,
+            },
+        });
+
+        serde_json::to_writer(&mut *out, &output)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use std::io::{Result, Write};
+
+use super::*;
+use crate::cop::Base;
+
+// JUnit XML report with a test case per cop for each inspected file.
+pub struct JUnitFormatter {
+    cop_names: Vec<&'static str>,
+}
+
+impl JUnitFormatter {
+    pub fn new(cops: &[&dyn Base]) -> JUnitFormatter {
+        JUnitFormatter {
+            cop_names: cops.iter().map(|cop| cop.name()).collect(),
+        }
+    }
+}
+
+impl Formatter for JUnitFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        let summary = Summary::new(files);
+        let tests = files.len() * self.cop_names.len();
+
+        writeln!(out, "<?xml version='1.0'?>")?;
+        writeln!(out, "<testsuites>")?;
+        writeln!(
+            out,
+            "  <testsuite name='rubocop' tests='{tests}' failures='{}'>",
+            summary.offense_count
+        )?;
+
+        for (input, offenses) in files {
+            let offenses = reported_offenses(offenses);
+            let classname = input.name.rsplit_once('.').map_or(&*input.name, |(s, _)| s);
+            let classname = xml_escape(&classname.replace(['/', '\\'], "."));
+
+            for &cop_name in &self.cop_names {
+                let name = xml_escape(cop_name);
+                let mut failures = offenses
+                    .iter()
+                    .filter(|o| o.cop_name == cop_name)
+                    .peekable();
+                if failures.peek().is_none() {
+                    writeln!(out, "    <testcase classname='{classname}' name='{name}'/>")?;
+                    continue;
+                }
+
+                writeln!(out, "    <testcase classname='{classname}' name='{name}'>")?;
+                for offense in failures {
+                    let (line, col) = input.line_col_for_pos(offense.loc.begin);
+                    let message = xml_escape(&offense.message);
+                    writeln!(out, "      <failure type='{name}' message='{message}'>")?;
+                    writeln!(
+                        out,
+                        "        {}:{}:{}",
+                        xml_escape(&input.name),
+                        line + 1,
+                        col + 1
+                    )?;
+                    writeln!(out, "      </failure>")?;
+                }
+                writeln!(out, "    </testcase>")?;
+            }
+        }
+
+        writeln!(out, "  </testsuite>")?;
+        writeln!(out, "</testsuites>")
+    }
+}
@@ -0,0 +1,97 @@
+#[allow(dead_code)]
+mod color;
+use color::*;
+
+mod checkstyle;
+pub use checkstyle::*;
+
+mod clang;
+pub use clang::*;
+
+mod github;
+pub use github::*;
+
+mod json;
+pub use json::*;
+
+mod junit;
+pub use junit::*;
+
+mod sarif;
+pub use sarif::*;
+
+use lib_ruby_parser::Loc;
+
+use crate::cop::{Offense, Severity};
+use crate::source::DecodedInput;
+
+pub trait Formatter {
+    fn format(
+        &self,
+        out: &mut dyn std::io::Write,
+        files: &[(DecodedInput, Vec<Offense>)],
+    ) -> std::io::Result<()>;
+}
+
+// Offenses at or above `fail_level` are reported as errors by formatters
+// which only distinguish warnings from errors.
+pub fn formatter_for(
+    name: &str,
+    no_color: bool,
+    fail_level: Severity,
+) -> Option<Box<dyn Formatter>> {
+    match name {
+        "c" | "clang" => Some(Box::new(ClangStyleFormatter::new(no_color))),
+        "checkstyle" => Some(Box::new(CheckstyleFormatter)),
+        "github" => Some(Box::new(GitHubActionsFormatter::new(fail_level))),
+        "j" | "json" => Some(Box::new(JsonFormatter)),
+        "junit" => Some(Box::new(JUnitFormatter::new(crate::default::cops()))),
+        "sarif" => Some(Box::new(SarifFormatter::new(crate::default::cops()))),
+        _ => None,
+    }
+}
+
+pub struct Summary {
+    pub file_count: usize,
+    pub offense_count: usize,
+    pub corrected_count: usize,
+    pub correctable_count: usize,
+}
+
+impl Summary {
+    pub fn new(files: &[(DecodedInput, Vec<Offense>)]) -> Summary {
+        let offenses = files.iter().flat_map(|(_, v)| v).filter(|o| !o.disabled);
+        let offenses = offenses.collect::<Vec<_>>();
+        Summary {
+            file_count: files.len(),
+            offense_count: offenses.len(),
+            corrected_count: offenses.iter().filter(|o| o.corrected).count(),
+            correctable_count: {
+                let correctable = offenses.iter().filter(|o| o.correctable && !o.corrected);
+                correctable.count()
+            },
+        }
+    }
+}
+
+// Offenses that weren't disabled by comments, in order of location.
+pub fn reported_offenses(offenses: &[Offense]) -> Vec<&Offense> {
+    let mut offenses = offenses.iter().filter(|o| !o.disabled).collect::<Vec<_>>();
+    offenses.sort_by_key(|Offense { loc, .. }| (loc.begin, loc.end));
+    offenses
+}
+
+pub fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,121 @@
+use std::io::{Result, Write};
+
+use serde_json::{json, Value};
+
+use super::*;
+use crate::cop::Base;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+// SARIF 2.1.0 log with a single run, as consumed by code scanning tools.
+pub struct SarifFormatter {
+    rules: Vec<(&'static str, Option<String>)>,
+}
+
+impl SarifFormatter {
+    pub fn new(cops: &[&dyn Base]) -> SarifFormatter {
+        let config = crate::default::config();
+        let rules = cops.iter().map(|cop| {
+            let description = config[cop.name()]["Description"].as_str();
+            (cop.name(), description.map(String::from))
+        });
+        SarifFormatter {
+            rules: rules.collect(),
+        }
+    }
+
+    fn rule_json(&self, name: &str, description: Option<&str>) -> Value {
+        let mut rule = json!({
+            "id": name,
+            "name": name,
+        });
+        if let Some(description) = description {
+            rule["shortDescription"] = json!({ "text": description });
+        }
+        rule
+    }
+
+    fn result_json(&self, input: &DecodedInput, offense: &Offense) -> Value {
+        let uri = input.name.replace('\\', "/");
+        let mut result = json!({
+            "ruleId": offense.cop_name,
+            "level": match offense.severity {
+                Severity::Info | Severity::Refactor | Severity::Convention => "note",
+                Severity::Warning => "warning",
+                Severity::Error | Severity::Fatal => "error",
+            },
+            "message": { "text": offense.message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": self.region_json(input, offense.loc),
+                },
+            }],
+        });
+        let index = self
+            .rules
+            .iter()
+            .position(|(name, _)| *name == offense.cop_name);
+        if let Some(index) = index {
+            result["ruleIndex"] = json!(index);
+        }
+        if !offense.edits.is_empty() {
+            let replacements = offense.edits.iter().map(|(loc, text)| {
+                json!({
+                    "deletedRegion": self.region_json(input, *loc),
+                    "insertedContent": { "text": text },
+                })
+            });
+            result["fixes"] = json!([{
+                "description": { "text": format!("Autocorrect {}", offense.cop_name) },
+                "artifactChanges": [{
+                    "artifactLocation": { "uri": uri },
+                    "replacements": replacements.collect::<Vec<_>>(),
+                }],
+            }]);
+        }
+        result
+    }
+
+    fn region_json(&self, input: &DecodedInput, loc: Loc) -> Value {
+        let (start_line, start_column) = input.line_col_for_pos(loc.begin);
+        let (end_line, end_column) = input.line_col_for_pos(loc.end);
+        json!({
+            "startLine": start_line + 1,
+            "startColumn": start_column + 1,
+            "endLine": end_line + 1,
+            "endColumn": end_column + 1,
+        })
+    }
+}
+
+impl Formatter for SarifFormatter {
+    fn format(&self, out: &mut dyn Write, files: &[(DecodedInput, Vec<Offense>)]) -> Result<()> {
+        let rules = self.rules.iter();
+        let rules = rules.map(|(name, description)| self.rule_json(name, description.as_deref()));
+
+        let results = files.iter().flat_map(|(input, offenses)| {
+            let offenses = reported_offenses(offenses).into_iter();
+            offenses.map(move |o| self.result_json(input, o))
+        });
+
+        let output = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "RuboCop",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://rubocop.org",
+                        "rules": rules.collect::<Vec<_>>(),
+                    },
+                },
+                "results": results.collect::<Vec<_>>(),
+            }],
+        });
+
+        serde_json::to_writer(&mut *out, &output)?;
+        Ok(())
+    }
+}
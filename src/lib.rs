@@ -1,14 +1,42 @@
 pub mod ast;
 pub mod cop;
+pub mod debug;
 pub mod default;
 pub mod expect_offense;
+pub mod lsp;
 pub mod source;
 
+mod auto_gen_config;
+pub use auto_gen_config::*;
+
+mod comment_config;
+pub use comment_config::*;
+
 mod commissioner;
 pub use commissioner::*;
 
 mod config;
 pub use config::*;
 
-mod reporter;
-pub use reporter::*;
+mod config_store;
+pub use config_store::*;
+
+mod diff;
+pub use diff::*;
+
+mod exit_status;
+pub use exit_status::*;
+
+mod formatter;
+pub use formatter::*;
+
+mod options;
+pub use options::*;
+
+mod path_util;
+
+mod result_cache;
+pub use result_cache::*;
+
+mod runner;
+pub use runner::*;
@@ -0,0 +1,274 @@
+mod transport;
+pub use transport::*;
+
+use std::collections::HashMap;
+use std::io::{BufRead, Result, Write};
+
+use lib_ruby_parser::Loc;
+use serde_json::{json, Value};
+
+use crate::cop::{self, Offense, Severity};
+use crate::source::DecodedInput;
+use crate::{reported_offenses, Autocorrect, Options, Runner};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+
+// Serves the Language Server Protocol until the client sends `exit`. Open
+// documents are inspected from memory, using the configuration of the file
+// they were opened from.
+pub fn run(
+    options: &Options,
+    cops: &[&dyn cop::Base],
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<()> {
+    // Documents are inspected from memory, so results are never cached.
+    let diagnostics_options = Options {
+        cache: Some(false),
+        config: options.config.clone(),
+        ..Default::default()
+    };
+    let formatting_options = Options {
+        autocorrect: Autocorrect::Safe,
+        cache: Some(false),
+        config: options.config.clone(),
+        ..Default::default()
+    };
+
+    let mut server = LanguageServer {
+        runner: Runner::new(&diagnostics_options, cops),
+        formatting_runner: Runner::new(&formatting_options, cops),
+        documents: HashMap::new(),
+        shutdown: false,
+    };
+
+    while let Some(body) = read_body(input)? {
+        let message = match serde_json::from_slice::<Value>(&body) {
+            Ok(message) => message,
+            Err(error) => {
+                respond_error(output, &Value::Null, PARSE_ERROR, &error.to_string())?;
+                continue;
+            }
+        };
+        if message["method"] == "exit" {
+            break;
+        }
+        server.handle(output, &message)?;
+    }
+    Ok(())
+}
+
+struct LanguageServer<'a> {
+    runner: Runner<'a>,
+    formatting_runner: Runner<'a>,
+    documents: HashMap<String, String>,
+    shutdown: bool,
+}
+
+impl LanguageServer<'_> {
+    fn handle(&mut self, output: &mut dyn Write, message: &Value) -> Result<()> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        // Notifications have no id and get no response.
+        let Some(id) = message.get("id") else {
+            match method {
+                "textDocument/didOpen" => {
+                    let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                    self.documents.insert(uri.to_string(), text.to_string());
+                    self.publish_diagnostics(output, uri)?;
+                }
+                "textDocument/didChange" => {
+                    let changes = params["contentChanges"].as_array();
+                    let text = changes.and_then(|c| c.last()?["text"].as_str());
+                    if let Some(text) = text {
+                        self.documents.insert(uri.to_string(), text.to_string());
+                        self.publish_diagnostics(output, uri)?;
+                    }
+                }
+                "textDocument/didClose" => {
+                    self.documents.remove(uri);
+                    let params = json!({ "uri": uri, "diagnostics": [] });
+                    notify(output, "textDocument/publishDiagnostics", params)?;
+                }
+                _ => {}
+            }
+            return Ok(());
+        };
+
+        if self.shutdown {
+            return respond_error(output, id, INVALID_REQUEST, "server is shutting down");
+        }
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 1 },
+                    "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                    "documentFormattingProvider": true,
+                },
+                "serverInfo": {
+                    "name": "rubocop",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+            "shutdown" => {
+                self.shutdown = true;
+                Value::Null
+            }
+            "textDocument/codeAction" => self.code_actions(uri, &params["range"]),
+            "textDocument/formatting" => self.formatting(uri),
+            _ => return respond_error(output, id, METHOD_NOT_FOUND, "method not found"),
+        };
+        write_message(
+            output,
+            &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        )
+    }
+
+    fn inspect(&self, runner: &Runner, uri: &str) -> Option<(DecodedInput, Vec<Offense>, Vec<u8>)> {
+        let text = self.documents.get(uri)?;
+        let path = uri_to_path(uri);
+        match runner.inspect_stdin(&path, text.as_bytes().to_vec()) {
+            Ok((input, offenses, output)) => {
+                let output = output.unwrap_or_else(|| text.as_bytes().to_vec());
+                Some((input, offenses, output))
+            }
+            Err(error) => {
+                eprintln!("{error}");
+                None
+            }
+        }
+    }
+
+    fn publish_diagnostics(&self, output: &mut dyn Write, uri: &str) -> Result<()> {
+        let diagnostics = match self.inspect(&self.runner, uri) {
+            Some((input, offenses, _)) => {
+                let offenses = reported_offenses(&offenses).into_iter();
+                offenses.map(|o| diagnostic(&input, o)).collect()
+            }
+            None => Vec::new(),
+        };
+        let params = json!({ "uri": uri, "diagnostics": diagnostics });
+        notify(output, "textDocument/publishDiagnostics", params)
+    }
+
+    // Offers to correct the offenses of each cop with offenses in the range.
+    fn code_actions(&self, uri: &str, range: &Value) -> Value {
+        let Some((input, offenses, _)) = self.inspect(&self.runner, uri) else { return json!([]) };
+        let first_line = range["start"]["line"].as_u64().unwrap_or(0) as usize;
+        let last_line = range["end"]["line"].as_u64().unwrap_or(u64::MAX) as usize;
+
+        let mut offenses = reported_offenses(&offenses);
+        offenses.retain(|o| {
+            let (begin_line, _) = input.line_col_for_pos(o.loc.begin);
+            let (end_line, _) = input.line_col_for_pos(o.loc.end);
+            !o.edits.is_empty() && begin_line <= last_line && end_line >= first_line
+        });
+
+        // Unsafe corrections may change what the code does, so they are
+        // labelled and never preferred.
+        let path = uri_to_path(uri);
+        let actions = offenses.into_iter().map(|offense| {
+            let is_safe = self.runner.is_safe_autocorrect(&path, offense.cop_name);
+            let title = if is_safe {
+                format!("Autocorrect {}", offense.cop_name)
+            } else {
+                format!("Autocorrect {} (unsafe)", offense.cop_name)
+            };
+            let edits = offense.edits.iter().map(
+                |(loc, new_text)| json!({ "range": range_json(&input, *loc), "newText": new_text }),
+            );
+            json!({
+                "title": title,
+                "kind": "quickfix",
+                "isPreferred": is_safe,
+                "diagnostics": [diagnostic(&input, offense)],
+                "edit": {
+                    "changes": { uri: edits.collect::<Vec<_>>() },
+                },
+            })
+        });
+        Value::Array(actions.collect())
+    }
+
+    // Replaces the whole document with the result of safe autocorrection.
+    fn formatting(&self, uri: &str) -> Value {
+        let Some((input, _, output)) = self.inspect(&self.formatting_runner, uri) else {
+            return Value::Null;
+        };
+        if output == input.as_shared_bytes() {
+            return json!([]);
+        }
+        let loc = Loc {
+            begin: 0,
+            end: input.as_shared_bytes().len(),
+        };
+        let new_text = String::from_utf8_lossy(&output);
+        json!([{ "range": range_json(&input, loc), "newText": new_text }])
+    }
+}
+
+fn notify(output: &mut dyn Write, method: &str, params: Value) -> Result<()> {
+    let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+    write_message(output, &message)
+}
+
+fn respond_error(output: &mut dyn Write, id: &Value, code: i64, message: &str) -> Result<()> {
+    let error = json!({ "code": code, "message": message });
+    write_message(
+        output,
+        &json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    )
+}
+
+fn diagnostic(input: &DecodedInput, offense: &Offense) -> Value {
+    let severity = match offense.severity {
+        Severity::Fatal | Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Convention => 3,
+        Severity::Refactor | Severity::Info => 4,
+    };
+    json!({
+        "range": range_json(input, offense.loc),
+        "severity": severity,
+        "code": offense.cop_name,
+        "source": "RuboCop",
+        "message": offense.message,
+    })
+}
+
+fn range_json(input: &DecodedInput, loc: Loc) -> Value {
+    json!({ "start": position_json(input, loc.begin), "end": position_json(input, loc.end) })
+}
+
+// Characters are counted in UTF-16 code units.
+fn position_json(input: &DecodedInput, pos: usize) -> Value {
+    let (line, _) = input.line_col_for_pos(pos);
+    let line_start = input.lines[line].start;
+    let text = String::from_utf8_lossy(&input.as_shared_bytes()[line_start..pos]);
+    json!({ "line": line, "character": text.encode_utf16().count() })
+}
+
+fn uri_to_path(uri: &str) -> String {
+    let Some(path) = uri.strip_prefix("file://") else { return uri.to_string() };
+    let mut bytes = Vec::new();
+    let mut rest = path.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let hex = tail.get(..2).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(decoded) if b == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(b);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
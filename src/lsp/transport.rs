@@ -0,0 +1,50 @@
+use std::io::{BufRead, Error, ErrorKind, Result, Write};
+
+use serde_json::Value;
+
+// Reads a message framed by a `Content-Length` header. Returns `None` at the
+// end of the input.
+pub fn read_message(input: &mut dyn BufRead) -> Result<Option<Value>> {
+    let Some(body) = read_body(input)? else { return Ok(None) };
+    let message = serde_json::from_slice(&body);
+    message
+        .map(Some)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+// Reads the body of a message without parsing it, so that a malformed body
+// doesn't lose track of where the next message starts.
+pub fn read_body(input: &mut dyn BufRead) -> Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse().ok();
+            }
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "missing Content-Length header",
+        ));
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(body))
+}
+
+pub fn write_message(output: &mut dyn Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
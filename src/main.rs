@@ -1,85 +1,125 @@
-use lib_ruby_parser::{Parser, ParserOptions};
+use std::io::{Read, Write};
 
-use rubocop::cop::Offense;
-use rubocop::source::DecodedInput;
-use rubocop::{Commissioner, Config, Reporter};
+use rubocop::{Options, Runner};
 
 fn main() {
-    let mut files = Vec::new();
-
-    let mut args = std::env::args().skip(1).peekable();
-    match args.peek() {
-        Some(_) => {
-            for s in args {
-                investigate_recursive(&s, &[&s], &mut files);
-            }
-        }
-        None => {
-            investigate_recursive(std::env::current_dir().unwrap(), &[], &mut files);
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
         }
     };
 
+    if options.debug {
+        rubocop::debug::enable();
+    }
+
+    if options.lsp {
+        let cops = rubocop::default::cops();
+        let result = rubocop::lsp::run(
+            &options,
+            cops,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stdout().lock(),
+        );
+        if let Err(error) = result {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+        return;
+    }
+
     let no_color = match std::env::var("NO_COLOR") {
-        Ok(s) => s != "",
+        Ok(s) => !s.is_empty(),
         Err(_) => false,
     };
 
-    Reporter::new(no_color).print_report(files);
-}
-
-fn investigate_recursive<P>(
-    path: P,
-    components: &[&str],
-    files: &mut Vec<(DecodedInput, Vec<Offense>)>,
-) where
-    P: AsRef<std::path::Path>,
-{
-    let path = path.as_ref();
-    if path.is_file() {
-        let Some(extension) = path.extension() else { return };
-        if extension == "rb" {
-            let name = components.join(std::path::MAIN_SEPARATOR_STR);
-            files.push(investigate_file(path, name));
-        }
-    } else {
-        for entry in path.read_dir().unwrap() {
-            let entry = entry.unwrap();
+    // With `--diff`, the diff takes the place of the default report.
+    let mut formats = options.formats.clone();
+    if formats.is_empty() && !options.diff {
+        formats.push(("clang".into(), None));
+    }
 
-            let name = entry.file_name();
-            let name = name.to_string_lossy();
+    let mut formatters = Vec::new();
+    for (format, out) in formats {
+        let Some(formatter) =
+            rubocop::formatter_for(&format, no_color || out.is_some(), options.fail_level())
+        else {
+            eprintln!("invalid formatter: {format}");
+            std::process::exit(2);
+        };
+        formatters.push((formatter, out));
+    }
 
-            // TODO: config
-            static EXCLUDE: &[&str] = &["node_modules", "tmp", "vendor", ".git"];
-            if EXCLUDE.contains(&name.as_ref()) {
-                continue;
+    let runner = Runner::new(&options, rubocop::default::cops());
+    let (inspections, mut errors) = match &options.stdin {
+        Some(path) => {
+            let mut source = Vec::new();
+            let result = std::io::stdin().read_to_end(&mut source);
+            let result = result.map_err(Into::into);
+            match result.and_then(|_| runner.inspect_stdin(path, source)) {
+                Ok(inspection) => (vec![inspection], Vec::new()),
+                Err(error) => (Vec::new(), vec![error]),
             }
+        }
+        None => runner.run(),
+    };
+    let inspections = inspections
+        .into_iter()
+        .map(|(i, o, output)| ((i, o), output));
+    let (files, outputs): (Vec<_>, Vec<_>) = inspections.unzip();
 
-            let components = &[components, &[&name]].concat();
-            investigate_recursive(entry.path(), components, files);
+    if options.auto_gen_config {
+        let result = std::env::current_dir()
+            .and_then(|dir| rubocop::auto_gen_config(&dir, &files, options.exclude_limit));
+        if let Err(error) = result {
+            errors.push(error.into());
         }
     }
-}
 
-fn investigate_file<P>(path: P, buffer_name: String) -> (DecodedInput, Vec<Offense>)
-where
-    P: AsRef<std::path::Path>,
-{
-    let parser_options = ParserOptions {
-        buffer_name,
-        record_tokens: false,
-        ..Default::default()
-    };
+    for (formatter, out) in formatters {
+        let result = match out {
+            Some(out) => {
+                std::fs::File::create(out).and_then(|mut file| formatter.format(&mut file, &files))
+            }
+            None => formatter.format(&mut std::io::stdout().lock(), &files),
+        };
+        if let Err(error) = result {
+            errors.push(error.into());
+        }
+    }
 
-    let (input, offenses, corrector) = Commissioner::investigate(
-        rubocop::default::cops(),
-        Config::new(None),
-        Parser::new(std::fs::read(path).unwrap(), parser_options),
-    );
+    if options.diff {
+        let mut stdout = std::io::stdout().lock();
+        for ((input, _), output) in files.iter().zip(&outputs) {
+            let Some(output) = output else { continue };
+            let original = input.as_shared_bytes();
+            let result = rubocop::write_unified_diff(&mut stdout, &input.name, original, output);
+            if let Err(error) = result {
+                errors.push(error.into());
+            }
+        }
+    } else if options.stdin.is_some() && options.autocorrect.is_enabled() {
+        // Editors read the corrected source after the report.
+        let mut stdout = std::io::stdout().lock();
+        for ((input, _), output) in files.iter().zip(&outputs) {
+            let output = output.as_deref().unwrap_or(input.as_shared_bytes());
+            let result =
+                writeln!(stdout, "====================").and_then(|_| stdout.write_all(output));
+            if let Err(error) = result {
+                errors.push(error.into());
+            }
+        }
+    }
 
-    let output = corrector.process(input.as_shared_bytes());
-    let output = String::from_utf8_lossy(&output);
-    let _ = output;
-    // println!("{output}");
+    if !errors.is_empty() {
+        eprintln!();
+        eprintln!("{} error(s) occurred:", errors.len());
+        for error in &errors {
+            eprintln!("{error}");
+        }
+    }
 
-    (input, offenses)
+    std::process::exit(rubocop::exit_status(&options, &files, &outputs, &errors));
 }
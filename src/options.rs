@@ -0,0 +1,123 @@
+use crate::cop::Severity;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum Autocorrect {
+    #[default]
+    Off,
+    Safe,
+    All,
+}
+
+impl Autocorrect {
+    pub fn is_enabled(self) -> bool {
+        self != Autocorrect::Off
+    }
+}
+
+#[derive(Default)]
+pub struct Options {
+    pub auto_gen_config: bool,
+    pub autocorrect: Autocorrect,
+    pub cache: Option<bool>,
+    pub cache_root: Option<String>,
+    pub config: Option<String>,
+    pub debug: bool,
+    // Corrections are shown as a diff instead of being written.
+    pub diff: bool,
+    pub exclude_limit: Option<usize>,
+    pub fail_level: Option<Severity>,
+    // Formatter names, each with the file to write its output to.
+    pub formats: Vec<(String, Option<String>)>,
+    pub lsp: bool,
+    pub parallel: Option<bool>,
+    pub paths: Vec<String>,
+    // Name of the file whose source is read from stdin.
+    pub stdin: Option<String>,
+}
+
+impl Options {
+    // Offenses below this severity don't fail the run.
+    pub fn fail_level(&self) -> Severity {
+        self.fail_level.unwrap_or(Severity::Refactor)
+    }
+
+    // Results are cached unless disabled with `--cache false`.
+    pub fn is_cache_enabled(&self) -> bool {
+        self.cache.unwrap_or(true)
+    }
+
+    // Files are inspected in parallel unless disabled with `--no-parallel`.
+    pub fn is_parallel(&self) -> bool {
+        self.parallel.unwrap_or(true)
+    }
+
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || match args.next() {
+                Some(value) => Ok(value),
+                None => Err(format!("missing argument: {arg}")),
+            };
+
+            match arg.as_str() {
+                "-a" | "--autocorrect" => options.autocorrect = Autocorrect::Safe,
+                "-A" | "--autocorrect-all" => options.autocorrect = Autocorrect::All,
+                "-c" | "--config" => options.config = Some(value()?),
+                "-d" | "--debug" => options.debug = true,
+                "-f" | "--format" => options.formats.push((value()?, None)),
+                "-o" | "--out" => {
+                    let value = value()?;
+                    match options.formats.last_mut() {
+                        Some((_, out @ None)) => *out = Some(value),
+                        _ => options.formats.push(("clang".into(), Some(value))),
+                    }
+                }
+                "-s" | "--stdin" => options.stdin = Some(value()?),
+                "-P" | "--parallel" => options.parallel = Some(true),
+                "--no-parallel" => options.parallel = Some(false),
+                "--auto-gen-config" => options.auto_gen_config = true,
+                "--diff" | "--dry-run" => options.diff = true,
+                "--lsp" => options.lsp = true,
+                "--cache" => {
+                    let value = value()?;
+                    let cache = value
+                        .parse()
+                        .map_err(|_| format!("invalid argument: {arg} {value}"))?;
+                    options.cache = Some(cache);
+                }
+                "--cache-root" => options.cache_root = Some(value()?),
+                "--fail-level" => {
+                    let value = value()?;
+                    let severity =
+                        Severity::from_name(&value).or_else(|| Severity::from_code(&value));
+                    let severity =
+                        severity.ok_or_else(|| format!("invalid argument: {arg} {value}"))?;
+                    options.fail_level = Some(severity);
+                }
+                "--exclude-limit" => {
+                    let value = value()?;
+                    let limit = value
+                        .parse()
+                        .map_err(|_| format!("invalid argument: {arg} {value}"))?;
+                    options.exclude_limit = Some(limit);
+                }
+                s if s.starts_with('-') => {
+                    return Err(format!("invalid option: {s}"));
+                }
+                _ => options.paths.push(arg),
+            }
+        }
+
+        // A diff shows safe corrections unless all were asked for with `-A`.
+        if options.diff && !options.autocorrect.is_enabled() {
+            options.autocorrect = Autocorrect::Safe;
+        }
+
+        Ok(options)
+    }
+}
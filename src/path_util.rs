@@ -0,0 +1,130 @@
+use std::path::{Component, Path, PathBuf};
+
+pub fn absolute_path(path: &Path) -> PathBuf {
+    let path = match std::env::current_dir() {
+        Ok(cwd) => cwd.join(path),
+        Err(_) => path.to_path_buf(),
+    };
+    let components = path.components().filter(|c| *c != Component::CurDir);
+    components.collect()
+}
+
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+// Matches like Ruby's `File.fnmatch?` with `FNM_PATHNAME | FNM_EXTGLOB`:
+// wildcards don't match `/`, except for `**/` which matches any number of
+// directories, and `{a,b}` matches either alternative. Without
+// `FNM_DOTMATCH`, a leading `.` of a file or directory name is only matched
+// by a `.` in the pattern.
+pub fn match_path(pattern: &str, path: &str) -> bool {
+    if pattern == path {
+        return true;
+    }
+    if !is_glob(pattern) {
+        return false;
+    }
+    let patterns = expand_braces(pattern);
+    patterns
+        .iter()
+        .any(|p| fnmatch(p.as_bytes(), path.as_bytes(), true))
+}
+
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let bytes = pattern.as_bytes();
+    let Some(open) = bytes.iter().position(|&b| b == b'{') else {
+        return vec![pattern.to_string()];
+    };
+
+    let mut depth = 0;
+    let mut alternatives = Vec::new();
+    let mut start = open + 1;
+    for (i, &b) in bytes.iter().enumerate().skip(open) {
+        match b {
+            b'{' => depth += 1,
+            b',' if depth == 1 => {
+                alternatives.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    alternatives.push(&pattern[start..i]);
+                    let (prefix, suffix) = (&pattern[..open], &pattern[i + 1..]);
+                    let expanded = alternatives.iter().map(|a| format!("{prefix}{a}{suffix}"));
+                    return expanded.flat_map(|p| expand_braces(&p)).collect();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    vec![pattern.to_string()]
+}
+
+// `name_start` tells whether `path` starts a file or directory name.
+fn fnmatch(pattern: &[u8], path: &[u8], name_start: bool) -> bool {
+    let hidden = name_start && path.first() == Some(&b'.');
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            let mut path = path;
+            loop {
+                if fnmatch(rest, path, true) {
+                    return true;
+                }
+                if path.first() == Some(&b'.') {
+                    return false;
+                }
+                let Some(index) = path.iter().position(|&b| b == b'/') else { return false };
+                path = &path[index + 1..];
+            }
+        }
+        [b'*', rest @ ..] => {
+            for index in 0..=path.len() {
+                if fnmatch(rest, &path[index..], name_start && index == 0) {
+                    return true;
+                }
+                if hidden || path.get(index) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        [b'?', rest @ ..] => match path {
+            [c, path @ ..] if *c != b'/' && !hidden => fnmatch(rest, path, false),
+            _ => false,
+        },
+        [b'[', class @ ..] => match (path, class.iter().position(|&b| b == b']')) {
+            ([c, path @ ..], Some(end)) if *c != b'/' && !hidden => {
+                let (class, rest) = (&class[..end], &class[end + 1..]);
+                match_class(class, *c) && fnmatch(rest, path, false)
+            }
+            _ => false,
+        },
+        [b'\\', c, rest @ ..] | [c, rest @ ..] => match path {
+            [first, path @ ..] if first == c => fnmatch(rest, path, *c == b'/'),
+            _ => false,
+        },
+    }
+}
+
+fn match_class(class: &[u8], c: u8) -> bool {
+    let (negated, mut class) = match class {
+        [b'!' | b'^', class @ ..] => (true, class),
+        _ => (false, class),
+    };
+
+    let mut matched = false;
+    while let Some(&first) = class.first() {
+        if let [_, b'-', last, rest @ ..] = class {
+            matched |= (first..=*last).contains(&c);
+            class = rest;
+        } else {
+            matched |= first == c;
+            class = &class[1..];
+        }
+    }
+    matched != negated
+}
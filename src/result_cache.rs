@@ -0,0 +1,217 @@
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::cop::{self, Offense, Severity};
+use crate::source::DecodedInput;
+use crate::Config;
+
+// The 64-bit FNV-1a hash, which unlike the hasher of the standard library
+// is the same across builds.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> StableHasher {
+        StableHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn source_hash(source: &[u8]) -> String {
+    let mut hasher = StableHasher::new();
+    hasher.write(source);
+    format!("{:016x}", hasher.finish())
+}
+
+// Offenses found in previous runs, stored in a file per inspected file. The
+// directory depends on the version, the default configuration, the cops and
+// the executable, so that results of other builds are never used.
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    pub fn new(root: &Path, cops: &[&dyn cop::Base]) -> ResultCache {
+        let mut hasher = StableHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        crate::default::config().hash(&mut hasher);
+        for cop in cops {
+            cop.name().hash(&mut hasher);
+        }
+        // Cops may change between builds of the same version, which are told
+        // apart by their executable.
+        if let Ok(metadata) = std::env::current_exe().and_then(std::fs::metadata) {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+        let dir = root.join("rubocop_cache");
+        let dir = dir.join(format!("{:016x}", hasher.finish()));
+        ResultCache { dir }
+    }
+
+    // `$XDG_CACHE_HOME`, falling back to `~/.cache`.
+    pub fn default_root() -> PathBuf {
+        if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+            return PathBuf::from(dir);
+        }
+        match std::env::var_os("HOME") {
+            Some(home) => PathBuf::from(home).join(".cache"),
+            None => std::env::temp_dir(),
+        }
+    }
+
+    // Identifies the results for the given source of a file, as inspected with
+    // the given configuration.
+    pub fn key(
+        &self,
+        path: &Path,
+        buffer_name: &str,
+        config: Config,
+        cops: &[&dyn cop::Base],
+        source: &[u8],
+    ) -> PathBuf {
+        let mut hasher = StableHasher::new();
+        crate::path_util::absolute_path(path).hash(&mut hasher);
+        buffer_name.hash(&mut hasher);
+        config.yaml.hash(&mut hasher);
+        let buffer_path = Path::new(buffer_name);
+        for &cop in cops {
+            if config.is_cop_enabled(cop) && config.is_relevant_file(cop, buffer_path) {
+                cop.name().hash(&mut hasher);
+            }
+        }
+        source.hash(&mut hasher);
+        self.dir.join(format!("{:016x}", hasher.finish()))
+    }
+
+    pub fn load(
+        &self,
+        key: &Path,
+        buffer_name: &str,
+        source: &[u8],
+        cops: &[&dyn cop::Base],
+    ) -> Option<(DecodedInput, Vec<Offense>)> {
+        let bytes = std::fs::read(key).ok()?;
+        let value: Value = serde_json::from_slice(&bytes).ok()?;
+
+        // Keys may collide, so the results are only used for the source they
+        // were found in.
+        if value["source_length"].as_u64()? != source.len() as u64
+            || value["source_hash"].as_str()? != source_hash(source)
+        {
+            return None;
+        }
+        let offenses = value["offenses"].as_array()?.iter();
+        let offenses = offenses.map(|offense| {
+            let cop_name = offense["cop_name"].as_str()?;
+            let cop = cops.iter().find(|cop| cop.name() == cop_name)?;
+            let loc = lib_ruby_parser::Loc {
+                begin: offense["begin"].as_u64()? as usize,
+                end: offense["end"].as_u64()? as usize,
+            };
+            let edits = offense["edits"].as_array()?.iter().map(|edit| {
+                let loc = lib_ruby_parser::Loc {
+                    begin: edit[0].as_u64()? as usize,
+                    end: edit[1].as_u64()? as usize,
+                };
+                Some((loc, edit[2].as_str()?.to_string()))
+            });
+            Some(Offense {
+                loc,
+                correctable: offense["correctable"].as_bool()?,
+                corrected: false,
+                disabled: offense["disabled"].as_bool()?,
+                severity: Severity::from_name(offense["severity"].as_str()?)?,
+                cop_name: cop.name(),
+                message: offense["message"].as_str()?.to_string().into(),
+                edits: edits.collect::<Option<_>>()?,
+            })
+        });
+        let offenses = offenses.collect::<Option<Vec<_>>>()?;
+
+        let mut input = lib_ruby_parser::source::DecodedInput::named(buffer_name);
+        input.update_bytes(source.to_vec());
+        Some((input.into(), offenses))
+    }
+
+    // Corrected offenses aren't saved, since results are only cached when
+    // autocorrection is disabled.
+    pub fn save(&self, key: &Path, source: &[u8], offenses: &[Offense]) -> std::io::Result<()> {
+        let offenses = offenses.iter().map(|offense| {
+            let edits = offense.edits.iter();
+            let edits = edits.map(|(loc, replacement)| json!([loc.begin, loc.end, replacement]));
+            json!({
+                "begin": offense.loc.begin,
+                "end": offense.loc.end,
+                "correctable": offense.correctable,
+                "disabled": offense.disabled,
+                "severity": offense.severity.name(),
+                "cop_name": offense.cop_name,
+                "message": offense.message,
+                "edits": edits.collect::<Vec<_>>(),
+            })
+        });
+        let contents = json!({
+            "source_length": source.len(),
+            "source_hash": source_hash(source),
+            "offenses": offenses.collect::<Vec<_>>(),
+        });
+        let contents = contents.to_string();
+
+        // Results are written to a temporary file first so that a concurrent
+        // run never reads a partially written file.
+        std::fs::create_dir_all(&self.dir)?;
+        let temp = key.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp, contents)?;
+        std::fs::rename(&temp, key)
+    }
+
+    // Once there are more than `max_files` cached results, the least
+    // recently written half of them is removed, along with the directories
+    // of other builds that are left empty.
+    pub fn prune(&self, max_files: usize) -> std::io::Result<()> {
+        let Some(root) = self.dir.parent() else { return Ok(()) };
+        let entries = match std::fs::read_dir(root) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        // Entries may be removed by concurrent runs at any time.
+        let dirs = entries.flatten().map(|entry| entry.path());
+        let dirs = dirs.filter(|path| path.is_dir()).collect::<Vec<_>>();
+        let mut files = Vec::new();
+        for dir in &dirs {
+            let Ok(entries) = std::fs::read_dir(dir) else { continue };
+            for entry in entries.flatten() {
+                let modified = entry.metadata().and_then(|m| m.modified());
+                let Ok(modified) = modified else { continue };
+                files.push((modified, entry.path()));
+            }
+        }
+        if files.len() <= 1 || files.len() <= max_files {
+            return Ok(());
+        }
+
+        files.sort();
+        for (_, path) in files.iter().take(files.len() / 2 + 1) {
+            let _ = std::fs::remove_file(path);
+        }
+        for dir in dirs {
+            let _ = std::fs::remove_dir(dir);
+        }
+        Ok(())
+    }
+}
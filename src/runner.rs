@@ -0,0 +1,455 @@
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Once;
+
+use lib_ruby_parser::{Loc, Parser, ParserOptions};
+
+use crate::cop::{self, Offense};
+use crate::source::DecodedInput;
+use crate::{debug, Commissioner, Config, ConfigStore, Options, ResultCache};
+
+const MAX_ITERATIONS: usize = 200;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+pub type Inspection = (DecodedInput, Vec<Offense>, Option<Vec<u8>>);
+
+#[derive(Debug)]
+pub struct InfiniteCorrectionLoop {
+    pub path: String,
+    pub cop_names: Vec<Vec<&'static str>>,
+}
+
+impl std::fmt::Display for InfiniteCorrectionLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let root_cause = self.cop_names.iter().map(|names| names.join(", "));
+        let root_cause = root_cause.collect::<Vec<_>>().join(" -> ");
+        write!(
+            f,
+            "Infinite loop detected in {} and caused by {}",
+            self.path, root_cause
+        )
+    }
+}
+
+impl std::error::Error for InfiniteCorrectionLoop {}
+
+#[derive(Debug)]
+pub struct FileError {
+    pub path: PathBuf,
+    pub error: std::io::Error,
+}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for FileError {}
+
+pub struct Runner<'a> {
+    options: &'a Options,
+    cops: &'a [&'a dyn cop::Base],
+    config_store: ConfigStore,
+    cache: Option<ResultCache>,
+    pending_cops_warning: Once,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(options: &'a Options, cops: &'a [&'a dyn cop::Base]) -> Self {
+        let config_store = ConfigStore::new(options.config.as_ref().map(PathBuf::from))
+            .with_auto_gen_config(options.auto_gen_config);
+
+        // Files have to be inspected again to be corrected.
+        let cache = if options.is_cache_enabled() && !options.autocorrect.is_enabled() {
+            let root = options.cache_root.as_ref().map(PathBuf::from);
+            let root = root.unwrap_or_else(ResultCache::default_root);
+            Some(ResultCache::new(&root, cops))
+        } else {
+            None
+        };
+
+        Self {
+            options,
+            cops,
+            config_store,
+            cache,
+            pending_cops_warning: Once::new(),
+        }
+    }
+
+    // Errors don't stop the run, so that the remaining files are still
+    // inspected.
+    pub fn run(&self) -> (Vec<Inspection>, Vec<Error>) {
+        let mut targets = Vec::new();
+        let mut errors = Vec::new();
+
+        if let Some(cache) = &self.cache {
+            // Errors in the configuration are reported for the files it
+            // applies to.
+            let yaml = self.config_store.for_dir(Path::new("")).ok().flatten();
+            let config = Config::new(yaml.as_deref());
+            let max_files = config.for_all_cops()["MaxFilesInCache"].as_u64();
+            let max_files = max_files.map_or(usize::MAX, |n| n as usize);
+            if let Err(error) = cache.prune(max_files) {
+                debug!("Could not prune the result cache: {error}");
+            }
+        }
+
+        if self.options.paths.is_empty() {
+            match std::env::current_dir() {
+                Ok(path) => self.find_files(&path, &[], &mut targets, &mut errors),
+                Err(error) => errors.push(error.into()),
+            }
+        } else {
+            for s in &self.options.paths {
+                self.find_files(Path::new(s), &[s], &mut targets, &mut errors);
+            }
+        }
+
+        let results = if self.options.is_parallel() {
+            self.inspect_parallel(&targets)
+        } else {
+            let results = targets.iter();
+            let results = results.map(|(path, name)| self.inspect_file(path, name.clone()));
+            results.collect()
+        };
+
+        let mut files = Vec::new();
+        for result in results {
+            match result {
+                Ok(file) => files.push(file),
+                Err(error) => add_error(&mut errors, error),
+            }
+        }
+
+        files.sort_by(|(a, _, _), (b, _, _)| a.name.cmp(&b.name));
+        (files, errors)
+    }
+
+    // Collects the files to inspect along with their buffer names.
+    fn find_files(
+        &self,
+        path: &Path,
+        components: &[&str],
+        targets: &mut Vec<(PathBuf, String)>,
+        errors: &mut Vec<Error>,
+    ) {
+        if path.is_file() {
+            let name = components.join(std::path::MAIN_SEPARATOR_STR);
+            targets.push((path.to_path_buf(), name));
+            return;
+        }
+
+        let entries = match path.read_dir() {
+            Ok(entries) => entries,
+            Err(error) => {
+                let path = path.to_path_buf();
+                return add_error(errors, Box::new(FileError { path, error }));
+            }
+        };
+
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    let path = path.to_path_buf();
+                    add_error(errors, Box::new(FileError { path, error }));
+                    continue;
+                }
+            };
+            let path = entry.path();
+
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let components = &[components, &[&name]].concat();
+
+            let yaml = match self.config_store.for_file(&path) {
+                Ok(yaml) => yaml,
+                Err(error) => {
+                    add_error(errors, Box::new(error));
+                    continue;
+                }
+            };
+            let base_dir = self.config_store.base_dir_for_file(&path);
+            let config = Config::new(yaml.as_deref()).with_base_dir(&base_dir);
+            if path.is_dir() {
+                if !config.is_dir_to_exclude(&path) {
+                    self.find_files(&path, components, targets, errors);
+                }
+            } else if !config.is_file_to_exclude(&path) && is_ruby_file(config, &path) {
+                self.find_files(&path, components, targets, errors);
+            }
+        }
+    }
+
+    // Workers take the next file until none are left. Results are returned in
+    // the order of `targets` regardless of which worker inspected them.
+    fn inspect_parallel(&self, targets: &[(PathBuf, String)]) -> Vec<Result<Inspection, Error>> {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let workers = workers.min(targets.len());
+        let next = AtomicUsize::new(0);
+
+        let mut results = std::thread::scope(|scope| {
+            let worker = || {
+                let mut results = Vec::new();
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((path, name)) = targets.get(index) else { break };
+                    results.push((index, self.inspect_file(path, name.clone())));
+                }
+                results
+            };
+            let handles = (0..workers).map(|_| scope.spawn(worker));
+            let handles = handles.collect::<Vec<_>>();
+            let results = handles.into_iter().flat_map(|h| h.join().unwrap());
+            results.collect::<Vec<_>>()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    // Corrected files are written unless only a diff was asked for.
+    fn inspect_file(&self, path: &Path, buffer_name: String) -> Result<Inspection, Error> {
+        let yaml = self.config_store.for_file(path)?;
+        let base_dir = self.config_store.base_dir_for_file(path);
+        let config = Config::new(yaml.as_deref()).with_base_dir(&base_dir);
+        self.warn_on_pending_cops(config);
+
+        let file_error = |error| FileError {
+            path: path.to_path_buf(),
+            error,
+        };
+        let source = std::fs::read(path).map_err(file_error)?;
+
+        let cache = self.cache.as_ref().map(|cache| {
+            let key = cache.key(path, &buffer_name, config, self.cops, &source);
+            (cache, key)
+        });
+        if let Some((cache, key)) = &cache {
+            if let Some((input, offenses)) = cache.load(key, &buffer_name, &source, self.cops) {
+                return Ok((input, offenses, None));
+            }
+        }
+
+        let (input, offenses, output) = self.inspect_source(config, buffer_name, source)?;
+
+        if let Some((cache, key)) = &cache {
+            if let Err(error) = cache.save(key, input.as_shared_bytes(), &offenses) {
+                debug!("Could not cache results of {}: {error}", path.display());
+            }
+        }
+
+        if let Some(output) = output.as_ref().filter(|_| !self.options.diff) {
+            std::fs::write(path, output).map_err(file_error)?;
+        }
+
+        Ok((input, offenses, output))
+    }
+
+    // The source of editor buffers is inspected as if it were that of the
+    // file at `path`.
+    pub fn inspect_stdin(&self, path: &str, source: Vec<u8>) -> Result<Inspection, Error> {
+        let yaml = self.config_store.for_file(Path::new(path))?;
+        let base_dir = self.config_store.base_dir_for_file(Path::new(path));
+        let config = Config::new(yaml.as_deref()).with_base_dir(&base_dir);
+        self.warn_on_pending_cops(config);
+
+        self.inspect_source(config, path.to_string(), source)
+    }
+
+    // Whether corrections of the cop are applied by `-a` to the file at
+    // `path`.
+    pub fn is_safe_autocorrect(&self, path: &str, cop_name: &str) -> bool {
+        let Some(&cop) = self.cops.iter().find(|cop| cop.name() == cop_name) else { return false };
+        let Ok(yaml) = self.config_store.for_file(Path::new(path)) else { return false };
+        Config::new(yaml.as_deref()).is_safe_autocorrect(cop)
+    }
+
+    fn warn_on_pending_cops(&self, config: Config) {
+        if config.for_all_cops()["NewCops"] != "pending" {
+            return;
+        }
+
+        let pending_cops = self.cops.iter().filter(|&&cop| config.is_cop_pending(cop));
+        let pending_cops = pending_cops.collect::<Vec<_>>();
+        if pending_cops.is_empty() {
+            return;
+        }
+
+        self.pending_cops_warning.call_once(|| {
+            eprintln!("The following cops were added to RuboCop, but are not configured. Please set Enabled to either `true` or `false` in your `.rubocop.yml` file.");
+            eprintln!();
+            eprintln!("Please also note that you can opt-in to new cops by default by adding this to your config:");
+            eprintln!("  AllCops:");
+            eprintln!("    NewCops: enable");
+            eprintln!();
+            for &&cop in pending_cops.iter() {
+                let version = &config.for_cop(cop)["VersionAdded"];
+                let version = version.as_str().unwrap_or("?");
+                eprintln!("{}: # new in {version}", cop.name());
+                eprintln!("  Enabled: true");
+            }
+            eprintln!();
+            eprintln!("For more information: https://docs.rubocop.org/rubocop/versioning.html");
+        });
+    }
+
+    // Investigates the source until autocorrection stops changing it. Returns
+    // the original input, the offenses in terms of the original input, and
+    // the corrected source if it differs from the original.
+    pub fn inspect_source(
+        &self,
+        config: Config,
+        buffer_name: String,
+        source: Vec<u8>,
+    ) -> Result<Inspection, Error> {
+        let autocorrect = self.options.autocorrect;
+
+        let cops = self.cops.iter().copied();
+        let path = Path::new(&buffer_name);
+        let cops = cops.filter(|&cop| config.is_relevant_file(cop, path));
+        let cops = cops.collect::<Vec<_>>();
+
+        let mut original: Option<DecodedInput> = None;
+        let mut offenses: Vec<Offense> = Vec::new();
+        let mut edits_by_iteration: Vec<Vec<(Loc, String)>> = Vec::new();
+        let mut cop_names_by_iteration: Vec<Vec<&'static str>> = Vec::new();
+        let mut checksums: Vec<u64> = Vec::new();
+        let mut source = source;
+
+        loop {
+            let checksum = checksum(&source);
+            let loop_start = checksums.iter().position(|&c| c == checksum);
+            if loop_start.is_some() || checksums.len() >= MAX_ITERATIONS {
+                let loop_start = loop_start.unwrap_or(0);
+                return Err(Box::new(InfiniteCorrectionLoop {
+                    path: buffer_name,
+                    cop_names: cop_names_by_iteration.split_off(loop_start),
+                }));
+            }
+            checksums.push(checksum);
+
+            let parser_options = ParserOptions {
+                buffer_name: buffer_name.clone(),
+                record_tokens: false,
+                ..Default::default()
+            };
+            let parser = Parser::new(source, parser_options);
+            let (input, mut new_offenses, corrector) =
+                Commissioner::investigate(&cops, config, autocorrect, parser);
+
+            // Offenses that weren't corrected will be found again, so only the
+            // corrected ones are kept from previous iterations.
+            offenses.retain(|o| o.corrected);
+
+            for offense in new_offenses.iter_mut() {
+                for edits in edits_by_iteration.iter().rev() {
+                    let original_loc = |loc: Loc| Loc {
+                        begin: original_pos(edits, loc.begin),
+                        end: original_pos(edits, loc.end),
+                    };
+                    offense.loc = original_loc(offense.loc);
+                    for (loc, _) in offense.edits.iter_mut() {
+                        *loc = original_loc(*loc);
+                    }
+                }
+            }
+
+            let cop_names = new_offenses.iter().filter(|o| o.corrected);
+            let mut cop_names = cop_names.map(|o| o.cop_name).collect::<Vec<_>>();
+            cop_names.dedup();
+            cop_names_by_iteration.push(cop_names);
+
+            offenses.append(&mut new_offenses);
+
+            let mut corrected = None;
+            if autocorrect.is_enabled() && !corrector.is_empty() {
+                let edits = corrector.edits();
+                let output = corrector.process(input.as_shared_bytes());
+                if output != input.as_shared_bytes() {
+                    corrected = Some((edits, output));
+                }
+            }
+
+            let Some((edits, output)) = corrected else {
+                return Ok(match original {
+                    Some(original) => {
+                        let output = input.as_shared_bytes().to_vec();
+                        (original, offenses, Some(output))
+                    }
+                    None => (input, offenses, None),
+                });
+            };
+
+            edits_by_iteration.push(edits);
+            original.get_or_insert(input);
+            source = output;
+        }
+    }
+}
+
+// The same error, e.g. in a configuration file shared by many files, is only
+// reported once.
+fn add_error(errors: &mut Vec<Error>, error: Error) {
+    let message = error.to_string();
+    if !errors.iter().any(|e| e.to_string() == message) {
+        errors.push(error);
+    }
+}
+
+// Files without an extension are inspected if they have a Ruby shebang, e.g.
+// executables in `bin`.
+fn is_ruby_file(config: Config, path: &Path) -> bool {
+    if config.is_file_to_include(path) {
+        return true;
+    }
+    if path.extension().is_some() {
+        return false;
+    }
+
+    let Ok(file) = std::fs::File::open(path) else { return false };
+    let mut line = String::new();
+    if std::io::BufRead::read_line(&mut std::io::BufReader::new(file), &mut line).is_err() {
+        return false;
+    }
+    let Some(shebang) = line.strip_prefix("#!") else { return false };
+
+    let all_cops = config.for_all_cops();
+    let interpreters = all_cops["RubyInterpreters"].as_sequence();
+    let mut interpreters = interpreters
+        .into_iter()
+        .flatten()
+        .filter_map(|i| i.as_str());
+    interpreters.any(|i| {
+        shebang
+            .split_whitespace()
+            .any(|s| s.rsplit('/').next() == Some(i))
+    })
+}
+
+fn checksum(source: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Maps a position in corrected source back to the source the edits were
+// applied to. Positions inside replaced text map to the end of the range.
+fn original_pos(edits: &[(Loc, String)], pos: usize) -> usize {
+    let mut delta: isize = 0;
+    for (loc, replacement) in edits {
+        let begin = loc.begin.saturating_add_signed(delta);
+        if pos <= begin {
+            break;
+        }
+        if pos < begin + replacement.len() {
+            return loc.end;
+        }
+        delta += replacement.len() as isize - (loc.end - loc.begin) as isize;
+    }
+    pos.saturating_add_signed(-delta)
+}
@@ -1,6 +1,6 @@
 use lib_ruby_parser::Loc;
 
-use crate::source::Rewriter;
+use crate::source::{ClobberingError, Rewriter};
 
 pub struct Corrector {
     rewriter: Rewriter,
@@ -17,6 +17,10 @@ impl Corrector {
         self.rewriter.is_empty()
     }
 
+    pub fn merge(&mut self, other: Corrector) -> Result<(), ClobberingError> {
+        self.rewriter.merge(other.rewriter)
+    }
+
     pub fn replace<S: Into<String>>(&mut self, loc: Loc, content: S) {
         self.rewriter.replace(loc.begin, loc.end, content.into());
     }
@@ -58,6 +62,12 @@ impl Corrector {
         self.rewriter.remove(loc.end - size, loc.end);
     }
 
+    pub fn edits(&self) -> Vec<(Loc, String)> {
+        let edits = self.rewriter.edits().into_iter();
+        let edits = edits.map(|(begin, end, content)| (Loc { begin, end }, content));
+        edits.collect()
+    }
+
     pub fn process(self, code: &[u8]) -> Vec<u8> {
         self.rewriter.process(code)
     }
@@ -27,7 +27,7 @@ impl DecodedInput {
         self.inner.line_col_for_pos(pos).unwrap()
     }
 
-    pub fn source(&self, loc: Loc) -> Cow<str> {
+    pub fn source(&self, loc: Loc) -> Cow<'_, str> {
         String::from_utf8_lossy(&self.inner.bytes[loc.begin..loc.end])
     }
 
@@ -1,7 +1,47 @@
+#[derive(Clone)]
 pub struct Rewriter {
     action_root: Action,
 }
 
+#[derive(Debug)]
+pub enum ClobberingError {
+    CrossingInsertions { begin_pos: usize, end_pos: usize },
+    DifferentReplacements { begin_pos: usize, end_pos: usize },
+    SwallowedInsertions { begin_pos: usize, end_pos: usize },
+}
+
+impl std::fmt::Display for ClobberingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::CrossingInsertions { begin_pos, end_pos } => {
+                write!(f, "insertion crosses range {begin_pos}...{end_pos}")
+            }
+            Self::DifferentReplacements { begin_pos, end_pos } => {
+                write!(
+                    f,
+                    "different replacements for range {begin_pos}...{end_pos}"
+                )
+            }
+            Self::SwallowedInsertions { begin_pos, end_pos } => {
+                write!(
+                    f,
+                    "replacement of range {begin_pos}...{end_pos} swallows insertions"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClobberingError {}
+
+// Conflicting actions of a single rewriter are resolved in favor of the
+// latest one, but merging rewriters must not silently drop any changes.
+#[derive(Copy, Clone, PartialEq)]
+enum Policy {
+    Accept,
+    Raise,
+}
+
 #[derive(Clone, Default)]
 struct Action {
     begin_pos: usize,
@@ -72,6 +112,17 @@ impl Rewriter {
         self.wrap(pos, pos, "".to_string(), content);
     }
 
+    pub fn merge(&mut self, other: Rewriter) -> Result<(), ClobberingError> {
+        let mut action_root = self.action_root.clone();
+        action_root.combine(other.action_root, Policy::Raise)?;
+        self.action_root = action_root;
+        Ok(())
+    }
+
+    pub fn edits(&self) -> Vec<(usize, usize, String)> {
+        self.action_root.clone().ordered_replacements()
+    }
+
     pub fn process(self, code: &[u8]) -> Vec<u8> {
         let mut result = Vec::new();
         let mut last_end = 0;
@@ -85,20 +136,21 @@ impl Rewriter {
     }
 
     fn combine(&mut self, action: Action) {
-        self.action_root.combine(action);
+        let result = self.action_root.combine(action, Policy::Accept);
+        debug_assert!(result.is_ok());
     }
 }
 
 impl Action {
-    fn combine(&mut self, action: Action) {
+    fn combine(&mut self, action: Action, policy: Policy) -> Result<(), ClobberingError> {
         if action.is_empty() {
-            return;
+            return Ok(());
         }
 
         if (action.begin_pos, action.end_pos) == (self.begin_pos, self.end_pos) {
-            self.merge(action)
+            self.merge(action, policy)
         } else {
-            self.place_in_hierarchy(action)
+            self.place_in_hierarchy(action, policy)
         }
     }
 
@@ -108,7 +160,7 @@ impl Action {
         self.insert_before.is_empty()
             && self.insert_after.is_empty()
             && self.children.is_empty()
-            && replacement.map_or(true, |r| r.is_empty() && self.begin_pos == self.end_pos)
+            && replacement.is_none_or(|r| r.is_empty() && self.begin_pos == self.end_pos)
     }
 
     fn ordered_replacements(self) -> Vec<(usize, usize, String)> {
@@ -132,39 +184,53 @@ impl Action {
             reps.append(&mut child.ordered_replacements());
         }
         if !insert_after.is_empty() {
-            reps.push((begin_pos, begin_pos, insert_after));
+            reps.push((end_pos, end_pos, insert_after));
         }
         reps
     }
 
-    #[allow(dead_code)]
     fn has_insertion(&self) -> bool {
         let replacement = self.replacement.as_ref();
 
         !self.insert_before.is_empty()
             || !self.insert_after.is_empty()
-            || replacement.map_or(false, |r| !r.is_empty())
+            || replacement.is_some_and(|r| !r.is_empty())
     }
 
-    fn place_in_hierarchy(&mut self, mut action: Action) {
+    fn place_in_hierarchy(
+        &mut self,
+        mut action: Action,
+        policy: Policy,
+    ) -> Result<(), ClobberingError> {
         let family = self.analyze_hierarchy(&action);
         let mut siblings = family.sibling_left;
         let mut sibling_right = family.sibling_right;
 
         if let Some(fusible) = family.fusible {
+            if policy == Policy::Raise {
+                for child in fusible.iter() {
+                    if action.has_insertion() || child.has_insertion() {
+                        return Err(ClobberingError::CrossingInsertions {
+                            begin_pos: child.begin_pos,
+                            end_pos: child.end_pos,
+                        });
+                    }
+                }
+            }
             if let Some(mut child) = family.child {
                 siblings.append(&mut child);
             }
             siblings.append(&mut sibling_right);
-            self.fuse_deletions(action, fusible, siblings)
+            self.fuse_deletions(action, fusible, siblings, policy)
         } else {
             let extra_sibling = if let Some(mut parent) = family.parent {
-                parent.combine(action);
+                parent.combine(action, policy)?;
                 parent
             } else if let Some(child) = family.child {
                 let tmp = action.children;
                 action.children = child;
-                action.combine_children(tmp);
+                action.swallow_children(policy)?;
+                action.combine_children(tmp, policy)?;
                 action
             } else {
                 action
@@ -172,13 +238,34 @@ impl Action {
             siblings.push(extra_sibling);
             siblings.append(&mut sibling_right);
             self.children = siblings;
+            self.swallow_children(policy)
         }
     }
 
-    fn combine_children(&mut self, more_children: Vec<Action>) {
+    fn combine_children(
+        &mut self,
+        more_children: Vec<Action>,
+        policy: Policy,
+    ) -> Result<(), ClobberingError> {
         for new_child in more_children {
-            self.place_in_hierarchy(new_child);
+            self.place_in_hierarchy(new_child, policy)?;
+        }
+        Ok(())
+    }
+
+    // A replacement takes the place of everything within its range.
+    fn swallow_children(&mut self, policy: Policy) -> Result<(), ClobberingError> {
+        if self.replacement.is_none() {
+            return Ok(());
         }
+        if policy == Policy::Raise && self.children.iter().any(|c| c.has_insertion()) {
+            return Err(ClobberingError::SwallowedInsertions {
+                begin_pos: self.begin_pos,
+                end_pos: self.end_pos,
+            });
+        }
+        self.children = Vec::new();
+        Ok(())
     }
 
     fn fuse_deletions(
@@ -186,7 +273,8 @@ impl Action {
         action: Action,
         mut fusible: Vec<Action>,
         other_siblings: Vec<Action>,
-    ) {
+        policy: Policy,
+    ) -> Result<(), ClobberingError> {
         self.children = other_siblings;
 
         fusible.insert(0, action.clone());
@@ -195,7 +283,7 @@ impl Action {
         fused_deletion.begin_pos = fusible.iter().map(|a| a.begin_pos).min().unwrap();
         fused_deletion.end_pos = fusible.iter().map(|a| a.end_pos).max().unwrap();
 
-        self.combine(fused_deletion)
+        self.combine(fused_deletion, policy)
     }
 
     fn bsearch_child_index<F>(&self, from: usize, f: F) -> usize
@@ -261,15 +349,21 @@ impl Action {
         }
     }
 
-    fn merge(&mut self, action: Action) {
+    fn merge(&mut self, action: Action, policy: Policy) -> Result<(), ClobberingError> {
+        if let (Some(a), Some(b)) = (&self.replacement, &action.replacement) {
+            if policy == Policy::Raise && a != b {
+                return Err(ClobberingError::DifferentReplacements {
+                    begin_pos: self.begin_pos,
+                    end_pos: self.end_pos,
+                });
+            }
+        }
         if action.replacement.is_some() {
             self.replacement = action.replacement;
         }
-        if self.replacement.is_some() {
-            self.children = Vec::new();
-        }
+        self.swallow_children(policy)?;
         self.insert_before.insert_str(0, &action.insert_before);
         self.insert_after.push_str(&action.insert_after);
-        self.combine_children(action.children)
+        self.combine_children(action.children, policy)
     }
 }
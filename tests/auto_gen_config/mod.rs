@@ -0,0 +1,129 @@
+use rubocop::*;
+
+use crate::common::*;
+
+#[test]
+fn test_todo_file_contents() {
+    let dir = temp_dir("todo-file");
+    let files = inspect(
+        &dir,
+        &[
+            ("lib/a.rb", "not x\nnot y\n"),
+            (
+                "lib/b.rb",
+                "not x # rubocop:disable Style/Not\nputs \"z\"\n",
+            ),
+            ("lib/c.rb", "puts \"z\"\n"),
+        ],
+    );
+
+    let contents = todo_file_contents(&dir, &files, Some(1));
+    let version = env!("CARGO_PKG_VERSION");
+    let expected = format!(
+        "\
+# This configuration was generated by
+# `rubocop --auto-gen-config --exclude-limit 1`
+# using RuboCop version {version}.
+# The point is for the user to remove these configuration records
+# one by one as the offenses are removed from the code base.
+# Note that changes in the inspected code, or installation of new
+# versions of RuboCop, may require this file to be generated again.
+
+# Offense count: 2
+# This cop supports safe autocorrection (--autocorrect).
+Style/Not:
+  Exclude:
+    - 'lib/a.rb'
+
+# Offense count: 2
+# This cop supports safe autocorrection (--autocorrect).
+Style/StringLiterals:
+  Enabled: false
+"
+    );
+    assert_eq!(contents, expected);
+}
+
+#[test]
+fn test_add_inheritance() {
+    let dir = temp_dir("add-inheritance");
+    let files = inspect(&dir, &[("a.rb", "not x\n")]);
+
+    auto_gen_config(&dir, &files, None).unwrap();
+    let todo = std::fs::read_to_string(dir.join(AUTO_GENERATED_FILE)).unwrap();
+    assert!(todo.ends_with("Style/Not:\n  Exclude:\n    - 'a.rb'\n"));
+    let dotfile = std::fs::read_to_string(dir.join(DOTFILE)).unwrap();
+    assert_eq!(dotfile, "inherit_from: .rubocop_todo.yml\n\n");
+
+    std::fs::write(
+        dir.join(DOTFILE),
+        "inherit_from: base.yml\nAllCops:\n  NewCops: enable\n",
+    )
+    .unwrap();
+    auto_gen_config(&dir, &files, None).unwrap();
+    let dotfile = std::fs::read_to_string(dir.join(DOTFILE)).unwrap();
+    assert_eq!(
+        dotfile,
+        "inherit_from:\n  - .rubocop_todo.yml\n  - base.yml\nAllCops:\n  NewCops: enable\n"
+    );
+
+    auto_gen_config(&dir, &files, None).unwrap();
+    let unchanged = std::fs::read_to_string(dir.join(DOTFILE)).unwrap();
+    assert_eq!(unchanged, dotfile);
+}
+
+#[test]
+fn test_todo_file_not_inherited() {
+    let dir = temp_dir("todo-not-inherited");
+    std::fs::write(dir.join(DOTFILE), "inherit_from: .rubocop_todo.yml\n").unwrap();
+    std::fs::write(
+        dir.join(AUTO_GENERATED_FILE),
+        "Style/Not:\n  Enabled: false\n",
+    )
+    .unwrap();
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("a.rb")).unwrap();
+    assert!(!Config::new(yaml.as_deref()).is_cop_enabled(&cop::style::Not));
+
+    let store = ConfigStore::new(None).with_auto_gen_config(true);
+    let yaml = store.for_file(&dir.join("a.rb")).unwrap();
+    assert!(Config::new(yaml.as_deref()).is_cop_enabled(&cop::style::Not));
+}
+
+#[test]
+fn test_unsafe_autocorrection_comment() {
+    let dir = temp_dir("todo-unsafe");
+    std::fs::write(dir.join(DOTFILE), "Style/Not:\n  SafeAutoCorrect: false\n").unwrap();
+    let files = inspect(&dir, &[("a.rb", "not x\nputs \"z\"\n")]);
+
+    let contents = todo_file_contents(&dir, &files, None);
+    assert!(contents
+        .contains("# This cop supports unsafe autocorrection (--autocorrect-all).\nStyle/Not:\n"));
+    assert!(contents.contains(
+        "# This cop supports safe autocorrection (--autocorrect).\nStyle/StringLiterals:\n"
+    ));
+}
+
+#[test]
+fn test_syntax_errors_are_not_disabled() {
+    let dir = temp_dir("todo-syntax");
+    let files = inspect(&dir, &[("a.rb", "def (\n"), ("b.rb", "not x\n")]);
+    let contents = todo_file_contents(&dir, &files, None);
+    assert!(contents.contains("Style/Not:"));
+    assert!(!contents.contains("Lint/Syntax"));
+}
+
+#[test]
+fn test_json_output() {
+    let dir = temp_dir("todo-json");
+    write(&dir.join("a.rb"), "not x\n");
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_rubocop"))
+        .args(["--auto-gen-config", "--format", "json", "--cache", "false"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["summary"]["offense_count"], 1);
+    assert!(dir.join(AUTO_GENERATED_FILE).exists());
+}
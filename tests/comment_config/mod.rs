@@ -0,0 +1,116 @@
+use rubocop::*;
+
+#[test]
+fn test_parse_directive() {
+    let parse = DirectiveComment::parse;
+    let names = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        parse("# rubocop:disable Style/Not"),
+        Some((DirectiveMode::Disable, names(&["Style/Not"])))
+    );
+    assert_eq!(
+        parse("# rubocop : enable Style/Not, Layout -- reason"),
+        Some((DirectiveMode::Enable, names(&["Style/Not", "Layout"])))
+    );
+    assert_eq!(
+        parse("#rubocop:todo all"),
+        Some((DirectiveMode::Todo, names(&["all"])))
+    );
+    assert_eq!(
+        parse("# rubocop:disable Style/Not because legacy"),
+        Some((DirectiveMode::Disable, names(&["Style/Not"])))
+    );
+    assert_eq!(
+        parse("# rubocop:disable Style/Not, Layout/EndAlignment--reason"),
+        Some((
+            DirectiveMode::Disable,
+            names(&["Style/Not", "Layout/EndAlignment"])
+        ))
+    );
+    assert_eq!(parse("# rubocop:disable"), None);
+    assert_eq!(parse("# rubocop:disable -- reason"), None);
+    assert_eq!(parse("# rubocop:ignore Style/Not"), None);
+    assert_eq!(parse("# disable Style/Not"), None);
+}
+
+#[test]
+fn test_disabled_offenses() {
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let config = Config::new(None);
+
+    let source = "\
+not a # rubocop:disable Style/Not because legacy
+not b
+# rubocop:disable Style
+x = \"c\"
+not d
+# rubocop:enable Style
+not e
+# rubocop:todo all
+not f
+";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (input, offenses, output) = result.unwrap();
+
+    let offenses = offenses.iter().map(|o| {
+        let (line, _) = input.line_col_for_pos(o.loc.begin);
+        (line + 1, o.cop_name, o.disabled, o.corrected)
+    });
+    let mut offenses = offenses.collect::<Vec<_>>();
+    offenses.sort();
+    assert_eq!(
+        offenses,
+        [
+            (1, "Style/Not", true, false),
+            (2, "Style/Not", false, true),
+            (4, "Style/StringLiterals", true, false),
+            (5, "Style/Not", true, false),
+            (7, "Style/Not", false, true),
+            (8, "Lint/MissingCopEnableDirective", false, false),
+            (9, "Style/Not", true, false),
+        ]
+    );
+
+    let expected = source.replace("not b", "!b").replace("not e", "!e");
+    assert_eq!(String::from_utf8(output.unwrap()).unwrap(), expected);
+}
+
+#[test]
+fn test_cop_enabled_within_department() {
+    let options = Options::default();
+    let config = Config::new(None);
+
+    let source = "\
+x = 1
+# rubocop:disable Style
+# rubocop:enable Style/Not
+not y
+x = \"z\"
+# rubocop:disable Style/Not
+not z
+# rubocop:enable Style
+";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (input, offenses, _) = result.unwrap();
+
+    let offenses = offenses.iter().map(|o| {
+        let (line, _) = input.line_col_for_pos(o.loc.begin);
+        (line + 1, o.cop_name, o.disabled)
+    });
+    let mut offenses = offenses.collect::<Vec<_>>();
+    offenses.sort();
+    assert_eq!(
+        offenses,
+        [
+            (4, "Style/Not", false),
+            (5, "Style/StringLiterals", true),
+            (7, "Style/Not", true),
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+use std::path::{Path, PathBuf};
+
+use rubocop::*;
+
+// A fresh directory under the system temporary directory, removed along
+// with its contents when dropped.
+pub struct TempDir(PathBuf);
+
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub fn temp_dir(name: &str) -> TempDir {
+    let dir = std::env::temp_dir().join(format!("rubocop-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    TempDir(dir)
+}
+
+// Writes a file, creating its parent directories.
+pub fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+// Inspects sources as if they were files in `dir`, with the default
+// configuration.
+pub fn inspect(
+    dir: &Path,
+    files: &[(&str, &str)],
+) -> Vec<(source::DecodedInput, Vec<cop::Offense>)> {
+    let options = Options::default();
+    let runner = Runner::new(&options, default::cops());
+    let inspect = |&(name, source): &(&str, &str)| {
+        let name = dir.join(name).to_string_lossy().into_owned();
+        let result = runner.inspect_source(Config::new(None), name, source.into());
+        let (input, offenses, _) = result.unwrap();
+        (input, offenses)
+    };
+    files.iter().map(inspect).collect()
+}
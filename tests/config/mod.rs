@@ -0,0 +1,304 @@
+use std::path::Path;
+
+use rubocop::*;
+
+use crate::common::*;
+
+#[test]
+fn test_find_config_in_parent_directory() {
+    let dir = temp_dir("find-config");
+    write(&dir.join(DOTFILE), "Style/Not:\n  Enabled: false\n");
+    write(&dir.join("lib/foo/bar.rb"), "");
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("lib/foo/bar.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+    assert!(!config.is_cop_enabled(&cop::style::Not));
+    assert!(config.is_cop_enabled(&cop::style::StringLiterals));
+}
+
+#[test]
+fn test_options_config_takes_precedence() {
+    let dir = temp_dir("options-config");
+    write(&dir.join(DOTFILE), "Style/Not:\n  Enabled: false\n");
+    write(
+        &dir.join("custom.yml"),
+        "Style/StringLiterals:\n  Enabled: false\n",
+    );
+
+    let store = ConfigStore::new(Some(dir.join("custom.yml")));
+    let yaml = store.for_file(&dir.join("foo.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+    assert!(config.is_cop_enabled(&cop::style::Not));
+    assert!(!config.is_cop_enabled(&cop::style::StringLiterals));
+}
+
+#[test]
+fn test_invalid_config() {
+    let dir = temp_dir("invalid-config");
+    write(&dir.join(DOTFILE), "- Style/Not\n");
+
+    let store = ConfigStore::new(None);
+    assert!(store.for_file(&dir.join("foo.rb")).is_err());
+}
+
+#[test]
+fn test_inherit_from() {
+    let dir = temp_dir("inherit-from");
+    write(
+        &dir.join("base.yml"),
+        "
+        Style/Not:
+          Enabled: false
+        Style/StringLiterals:
+          Enabled: false
+          EnforcedStyle: double_quotes
+        ",
+    );
+    write(
+        &dir.join(DOTFILE),
+        "
+        inherit_from: base.yml
+        Style/StringLiterals:
+          Enabled: true
+        ",
+    );
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("foo.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+    assert!(!config.is_cop_enabled(&cop::style::Not));
+    assert!(config.is_cop_enabled(&cop::style::StringLiterals));
+
+    let cop_config = config.for_cop(&cop::style::StringLiterals);
+    assert_eq!(cop_config["EnforcedStyle"], "double_quotes");
+}
+
+#[test]
+fn test_inherit_mode_merge() {
+    let dir = temp_dir("inherit-mode");
+    write(
+        &dir.join("config/base.yml"),
+        "
+        Style/Not:
+          Exclude:
+            - a.rb
+        Style/StringLiterals:
+          Exclude:
+            - a.rb
+        ",
+    );
+    write(
+        &dir.join(DOTFILE),
+        "
+        inherit_from:
+          - config/base.yml
+        inherit_mode:
+          merge:
+            - Exclude
+        Style/Not:
+          Exclude:
+            - b.rb
+        Style/StringLiterals:
+          inherit_mode:
+            override:
+              - Exclude
+          Exclude:
+            - b.rb
+        ",
+    );
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("foo.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+
+    // Paths in files not named `.rubocop*` are relative to the current
+    // directory.
+    let a = std::env::current_dir().unwrap().join("a.rb");
+    let b = dir.join("b.rb");
+    let path = |p: &Path| serde_yaml::Value::from(p.to_string_lossy());
+
+    let cop_config = config.for_cop(&cop::style::Not);
+    let exclude = serde_yaml::Value::from(vec![path(&a), path(&b)]);
+    assert_eq!(cop_config["Exclude"], exclude);
+
+    let cop_config = config.for_cop(&cop::style::StringLiterals);
+    let exclude = serde_yaml::Value::from(vec![path(&b)]);
+    assert_eq!(cop_config["Exclude"], exclude);
+}
+
+#[test]
+fn test_circular_inherit_from() {
+    let dir = temp_dir("circular-inherit-from");
+    write(&dir.join(DOTFILE), "inherit_from: base.yml\n");
+    write(&dir.join("base.yml"), "inherit_from: .rubocop.yml\n");
+
+    let store = ConfigStore::new(None);
+    assert!(store.for_file(&dir.join("foo.rb")).is_err());
+}
+
+#[test]
+fn test_department_config() {
+    let yaml = serde_yaml::from_str(
+        "
+        Style:
+          Enabled: false
+          EnforcedStyle: double_quotes
+        Style/StringLiterals:
+          Enabled: true
+        ",
+    );
+    let yaml = yaml.unwrap();
+    let config = Config::new(Some(&yaml));
+
+    assert!(!config.is_cop_enabled(&cop::style::Not));
+    assert!(config.is_cop_enabled(&cop::style::StringLiterals));
+    assert!(config.is_cop_enabled(&cop::layout::EndAlignment));
+
+    let cop_config = config.for_cop(&cop::style::StringLiterals);
+    assert_eq!(cop_config["EnforcedStyle"], "double_quotes");
+}
+
+#[test]
+fn test_department_enabled() {
+    let yaml = serde_yaml::from_str("Style:\n  Enabled: true\n").unwrap();
+    let config = Config::new(Some(&yaml));
+    assert!(config.is_cop_enabled(&cop::style::Not));
+    assert!(config.is_cop_pending(&cop::style::RedundantFilterChain));
+    assert!(!config.is_cop_enabled(&cop::style::RedundantFilterChain));
+}
+
+#[test]
+fn test_cop_defaults_take_precedence_over_department() {
+    let default = serde_yaml::from_str("Style/Not:\n  Exclude:\n    - 'db/**/*'\n");
+    let default = Box::leak(Box::new(default.unwrap()));
+    let yaml = serde_yaml::from_str("Style:\n  Exclude:\n    - 'lib/**/*'\n").unwrap();
+    let config = Config::new(Some(&yaml)).with_default(default);
+
+    let not = &cop::style::Not;
+    assert!(!config.is_relevant_file(not, Path::new("db/foo.rb")));
+    assert!(config.is_relevant_file(not, Path::new("lib/foo.rb")));
+    let string_literals = &cop::style::StringLiterals;
+    assert!(!config.is_relevant_file(string_literals, Path::new("lib/foo.rb")));
+    assert!(config.is_relevant_file(string_literals, Path::new("db/foo.rb")));
+}
+
+#[test]
+fn test_pending_cops() {
+    let config = Config::new(None);
+    assert!(config.is_cop_pending(&cop::style::RedundantFilterChain));
+    assert!(!config.is_cop_enabled(&cop::style::RedundantFilterChain));
+
+    let yaml = serde_yaml::from_str("AllCops: { NewCops: enable }").unwrap();
+    let config = Config::new(Some(&yaml));
+    assert!(config.is_cop_enabled(&cop::style::RedundantFilterChain));
+
+    let yaml = serde_yaml::from_str(
+        "
+        AllCops:
+          NewCops: enable
+        Style/RedundantFilterChain:
+          Enabled: false
+        ",
+    );
+    let yaml = yaml.unwrap();
+    let config = Config::new(Some(&yaml));
+    assert!(!config.is_cop_pending(&cop::style::RedundantFilterChain));
+    assert!(!config.is_cop_enabled(&cop::style::RedundantFilterChain));
+}
+
+#[test]
+fn test_default_include_and_exclude() {
+    let config = Config::new(None);
+    assert!(config.is_file_to_include(Path::new("lib/foo.rb")));
+    assert!(config.is_file_to_include(Path::new("Gemfile")));
+    assert!(config.is_file_to_include(Path::new("foo/bar.gemspec")));
+    assert!(config.is_file_to_include(Path::new("lib/tasks/foo.rake")));
+    assert!(!config.is_file_to_include(Path::new("lib/foo.py")));
+    assert!(config.is_file_to_exclude(Path::new("vendor/bundle/foo.rb")));
+    assert!(!config.is_file_to_exclude(Path::new("lib/vendor.rb")));
+    assert!(config.is_dir_to_exclude(Path::new("node_modules")));
+    assert!(!config.is_dir_to_exclude(Path::new("lib")));
+}
+
+#[test]
+fn test_wildcards_skip_dotfiles() {
+    let config = Config::new(None);
+    assert!(!config.is_file_to_include(Path::new(".foo.rb")));
+    assert!(!config.is_file_to_include(Path::new(".hidden/foo.rb")));
+    assert!(!config.is_file_to_include(Path::new("lib/.hidden/foo.rb")));
+    assert!(config.is_file_to_include(Path::new(".irbrc")));
+    assert!(config.is_file_to_include(Path::new("lib/.irbrc")));
+    assert!(config.is_file_to_exclude(Path::new(".git/foo.rb")));
+}
+
+#[test]
+fn test_exclude_relative_to_config_file() {
+    let dir = temp_dir("exclude");
+    write(
+        &dir.join(DOTFILE),
+        "AllCops:\n  Exclude:\n    - '**/*.{rake,gemspec}'\n    - 'db/[a-m]*.rb'\n",
+    );
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("foo.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+    assert!(config.is_file_to_exclude(&dir.join("lib/tasks/foo.rake")));
+    assert!(config.is_file_to_exclude(&dir.join("foo.gemspec")));
+    assert!(config.is_file_to_exclude(&dir.join("db/foo.rb")));
+    assert!(!config.is_file_to_exclude(&dir.join("db/seeds.rb")));
+    assert!(!config.is_file_to_exclude(&dir.join("db/foo/bar.rb")));
+    assert!(!config.is_file_to_exclude(Path::new("db/foo.rb")));
+}
+
+#[test]
+fn test_cop_include_and_exclude() {
+    let dir = temp_dir("cop-exclude");
+    write(
+        &dir.join(DOTFILE),
+        "Layout/EndAlignment:\n  Exclude:\n    - db/schema.rb\nStyle/Not:\n  Include:\n    - 'app/**/*.rb'\n",
+    );
+
+    let store = ConfigStore::new(None);
+    let yaml = store.for_file(&dir.join("foo.rb")).unwrap();
+    let config = Config::new(yaml.as_deref());
+    let end_alignment = &cop::layout::EndAlignment;
+    assert!(!config.is_relevant_file(end_alignment, &dir.join("db/schema.rb")));
+    assert!(config.is_relevant_file(end_alignment, &dir.join("db/seeds.rb")));
+    assert!(config.is_relevant_file(&cop::style::Not, &dir.join("app/models/foo.rb")));
+    assert!(!config.is_relevant_file(&cop::style::Not, &dir.join("lib/foo.rb")));
+}
+
+#[test]
+fn test_severity() {
+    let yaml = serde_yaml::from_str("Style/Not:\n  Severity: error\nLayout:\n  Severity: info\n");
+    let yaml = yaml.unwrap();
+    let config = Config::new(Some(&yaml));
+    assert_eq!(config.severity(&cop::style::Not), cop::Severity::Error);
+    assert_eq!(
+        config.severity(&cop::style::StringLiterals),
+        cop::Severity::Convention
+    );
+    assert_eq!(
+        config.severity(&cop::lint::MissingCopEnableDirective),
+        cop::Severity::Warning
+    );
+    assert_eq!(
+        config.severity(&cop::layout::EndAlignment),
+        cop::Severity::Info
+    );
+}
+
+#[test]
+fn test_safe_autocorrect() {
+    let config = Config::new(None);
+    assert!(config.is_safe_autocorrect(&cop::style::Not));
+    assert!(!config.is_safe_autocorrect(&cop::style::RedundantFilterChain));
+
+    let yaml = serde_yaml::from_str("Style/Not:\n  Safe: false\nStyle/StringLiterals:\n  SafeAutoCorrect: false\nStyle/RedundantFilterChain:\n  SafeAutoCorrect: true\n");
+    let yaml = yaml.unwrap();
+    let config = Config::new(Some(&yaml));
+    assert!(!config.is_safe_autocorrect(&cop::style::Not));
+    assert!(!config.is_safe_autocorrect(&cop::style::StringLiterals));
+    assert!(config.is_safe_autocorrect(&cop::style::RedundantFilterChain));
+}
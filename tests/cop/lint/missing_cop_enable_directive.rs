@@ -0,0 +1,134 @@
+use rubocop::*;
+
+const COP: &dyn cop::Base = &cop::lint::MissingCopEnableDirective;
+
+#[test]
+fn test_missing_enable() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            # rubocop:disable Style/Not
+            ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Re-enable Style/Not cop with `# rubocop:enable` after disabling it.
+            not x
+            ";
+    }
+}
+
+#[test]
+fn test_missing_department_enable() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            # rubocop:disable Style
+            ^^^^^^^^^^^^^^^^^^^^^^^ Re-enable Style department with `# rubocop:enable` after disabling it.
+            not x
+            ";
+    }
+}
+
+#[test]
+fn test_missing_all_enable() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            # rubocop:disable all
+            ^^^^^^^^^^^^^^^^^^^^^ Re-enable all cops with `# rubocop:enable` after disabling it.
+            not x
+            ";
+    }
+
+    // The offense is on a line disabled by the directive it reports.
+    let options = Options::default();
+    let runner = Runner::new(&options, default::cops());
+    let source = "# rubocop:disable all\nnot x\n";
+    let result = runner.inspect_source(Config::new(None), "test.rb".into(), source.into());
+    let (_, offenses, _) = result.unwrap();
+    let offenses = offenses.iter().map(|o| (o.cop_name, o.disabled));
+    let offenses = offenses.collect::<Vec<_>>();
+    assert_eq!(
+        offenses,
+        [
+            ("Lint/MissingCopEnableDirective", false),
+            ("Style/Not", true),
+        ]
+    );
+}
+
+#[test]
+fn test_enabled_again() {
+    expect_no_offenses! {
+        cop = COP;
+        source =
+            "
+            # rubocop:disable Style/Not
+            not x
+            # rubocop:enable Style/Not
+            not y # rubocop:disable Style/Not
+            ";
+    }
+}
+
+#[test]
+fn test_disabled_in_config() {
+    expect_offense! {
+        config = config();
+        cop = COP;
+        source =
+            "
+            # rubocop:disable Style/Not
+            ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Re-enable Style/Not cop with `# rubocop:enable` after disabling it.
+            not x
+            ";
+    }
+}
+
+config!(
+    "
+    Style/Not:
+      Enabled: false
+    "
+);
+
+mod maximum_range_size {
+    use super::*;
+
+    config!(&format!(
+        "
+        {COP}:
+          MaximumRangeSize: 1
+        "
+    ));
+
+    #[test]
+    fn test_range_too_long() {
+        expect_offense! {
+            config = config();
+            cop = COP;
+            source =
+                "
+                # rubocop:disable Style/Not
+                ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Re-enable Style/Not cop within 1 lines after disabling it.
+                not x
+                not y
+                # rubocop:enable Style/Not
+                ";
+        }
+    }
+
+    #[test]
+    fn test_range_within_limit() {
+        expect_no_offenses! {
+            config = config();
+            cop = COP;
+            source =
+                "
+                # rubocop:disable Style/Not
+                not x
+                # rubocop:enable Style/Not
+                ";
+        }
+    }
+}
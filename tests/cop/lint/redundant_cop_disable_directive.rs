@@ -0,0 +1,109 @@
+use rubocop::*;
+
+const COP: &dyn cop::Base = &cop::lint::RedundantCopDisableDirective;
+
+#[test]
+fn test_redundant_line_end_directive() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            x = 1 # rubocop:disable Style/Not
+                  ^^^^^^^^^^^^^^^^^^^^^^^^^^^ Unnecessary disabling of `Style/Not`.
+            ";
+        correction =
+            "
+            x = 1
+            ";
+    }
+}
+
+#[test]
+fn test_redundant_range_directive() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            x = 1
+            # rubocop:disable Style, Foo/Bar
+            ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ Unnecessary disabling of `Style` department, `Foo/Bar` (unknown cop).
+            y = 2
+            # rubocop:enable Style, Foo/Bar
+            ";
+        correction =
+            "
+            x = 1
+            y = 2
+            # rubocop:enable Style, Foo/Bar
+            ";
+    }
+}
+
+#[test]
+fn test_redundant_all() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            x = 1 # rubocop:disable all
+                  ^^^^^^^^^^^^^^^^^^^^^ Unnecessary disabling of all cops.
+            ";
+        correction =
+            "
+            x = 1
+            ";
+    }
+}
+
+#[test]
+fn test_disabling_itself() {
+    expect_no_offenses! {
+        cop = COP;
+        source =
+            "
+            x = 1 # rubocop:disable Lint/RedundantCopDisableDirective
+            ";
+    }
+}
+
+fn inspect(source: &str) -> (Vec<(&'static str, bool)>, String) {
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let config = Config::new(None);
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (input, offenses, output) = result.unwrap();
+    let offenses = offenses.iter().map(|o| (o.cop_name, o.disabled));
+    let mut offenses = offenses.collect::<Vec<_>>();
+    offenses.sort();
+    let output = output.unwrap_or_else(|| input.as_shared_bytes().to_vec());
+    (offenses, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn test_necessary_directive() {
+    let source = "not x # rubocop:disable Style/Not\n";
+    let (offenses, output) = inspect(source);
+    assert_eq!(offenses, [("Style/Not", true)]);
+    assert_eq!(output, source);
+}
+
+#[test]
+fn test_partially_redundant_directive() {
+    let (offenses, output) = inspect(
+        "\
+not x # rubocop:disable Layout/EndAlignment, Style/Not, Style/StringLiterals
+",
+    );
+    assert_eq!(
+        offenses,
+        [
+            ("Lint/RedundantCopDisableDirective", false),
+            ("Lint/RedundantCopDisableDirective", false),
+            ("Style/Not", true),
+        ]
+    );
+    assert_eq!(output, "not x # rubocop:disable Style/Not\n");
+}
@@ -0,0 +1,64 @@
+use rubocop::*;
+
+const COP: &dyn cop::Base = &cop::lint::Syntax;
+
+#[test]
+fn test_syntax_error() {
+    expect_offense! {
+        cop = COP;
+        source =
+            "
+            puts(x, y))
+                      ^ unexpected tRPAREN
+            ";
+    }
+}
+
+#[test]
+fn test_warning() {
+    config!(
+        "
+        Lint/Syntax:
+          IncludeWarnings: true
+        "
+    );
+
+    expect_offense! {
+        config = config();
+        cop = COP;
+        source =
+            "
+            puts -x
+                 ^ ambiguous first argument; put parentheses or a space even after `-' operator
+            ";
+    }
+}
+
+#[test]
+fn test_no_offenses() {
+    expect_no_offenses! {
+        cop = COP;
+        source =
+            "
+            puts -x
+            ";
+    }
+}
+
+#[test]
+fn test_cannot_be_disabled() {
+    let yaml = serde_yaml::from_str("Lint/Syntax:\n  Enabled: false\n").unwrap();
+    let options = Options::default();
+    let runner = Runner::new(&options, default::cops());
+    let inspect = |source: &str| {
+        let config = Config::new(Some(&yaml));
+        let result = runner.inspect_source(config, "test.rb".into(), source.into());
+        let (_, offenses, _) = result.unwrap();
+        let offenses = offenses.iter().map(|o| (o.cop_name, o.disabled));
+        offenses.collect::<Vec<_>>()
+    };
+
+    let expected = [("Lint/Syntax", false)];
+    assert_eq!(inspect("# rubocop:disable all\ndef (\n"), expected);
+    assert_eq!(inspect("def ( # rubocop:disable Lint/Syntax\n"), expected);
+}
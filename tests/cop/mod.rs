@@ -1,2 +1,3 @@
 mod layout;
+mod lint;
 mod style;
@@ -0,0 +1,15 @@
+use lib_ruby_parser::Loc;
+use rubocop::source::Corrector;
+
+#[test]
+fn test_wrap() {
+    let code = b"foo bar baz";
+    let mut corrector = Corrector::new(code);
+    corrector.wrap(Loc { begin: 4, end: 7 }, "(", ")");
+
+    let edits = corrector.edits().into_iter();
+    let edits = edits.map(|(loc, s)| (loc.begin, loc.end, s));
+    let edits = edits.collect::<Vec<_>>();
+    assert_eq!(edits, [(4, 4, "(".into()), (7, 7, ")".into())]);
+    assert_eq!(corrector.process(code), b"foo (bar) baz");
+}
@@ -0,0 +1,71 @@
+use rubocop::*;
+
+use crate::common::*;
+
+fn diff(original: &str, corrected: &str) -> String {
+    let mut out = Vec::new();
+    write_unified_diff(&mut out, "a.rb", original.as_bytes(), corrected.as_bytes()).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_unified_diff() {
+    let original = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\n";
+    let corrected = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\n";
+    let expected = "\
+--- a.rb
++++ a.rb
+@@ -1,5 +1,5 @@
+ a
+-b
++B
+ c
+ d
+ e
+@@ -11,3 +11,4 @@
+ k
+ l
+ m
++n
+";
+    assert_eq!(diff(original, corrected), expected);
+    assert_eq!(diff(original, original), "");
+}
+
+#[test]
+fn test_unified_diff_without_newline_at_end_of_file() {
+    let expected = "\
+--- a.rb
++++ a.rb
+@@ -1 +1 @@
+-not x
+\\ No newline at end of file
++!x
+\\ No newline at end of file
+";
+    assert_eq!(diff("not x", "!x"), expected);
+}
+
+#[test]
+fn test_diff_does_not_write_files() {
+    let dir = temp_dir("diff");
+    write(&dir.join("foo.rb"), "not x\n");
+
+    let path = dir.join("foo.rb").to_string_lossy().into_owned();
+    let args = ["--diff".into(), "--cache".into(), "false".into(), path];
+    let options = Options::parse(args).unwrap();
+    assert_eq!(options.autocorrect, Autocorrect::Safe);
+    let runner = Runner::new(&options, default::cops());
+    let (files, errors) = runner.run();
+    assert!(errors.is_empty());
+
+    let (input, offenses, output) = &files[0];
+    assert_eq!(input.as_shared_bytes(), b"not x\n");
+    assert!(offenses[0].corrected);
+    assert_eq!(output.as_deref(), Some(&b"!x\n"[..]));
+    assert_eq!(std::fs::read(dir.join("foo.rb")).unwrap(), b"not x\n");
+
+    let options = Options::parse(["--dry-run", "-A"].map(String::from)).unwrap();
+    assert!(options.diff);
+    assert_eq!(options.autocorrect, Autocorrect::All);
+}
@@ -0,0 +1,46 @@
+use rubocop::*;
+
+fn inspect(options: &Options, source: &str) -> Inspection {
+    let runner = Runner::new(options, default::cops());
+    let result = runner.inspect_source(Config::new(None), "test.rb".into(), source.into());
+    result.unwrap()
+}
+
+fn status(args: &[&str], source: &str, errors: &[Error]) -> i32 {
+    let options = Options::parse(args.iter().map(|&arg| arg.into())).unwrap();
+    let (input, offenses, output) = inspect(&options, source);
+    exit_status(&options, &[(input, offenses)], &[output], errors)
+}
+
+#[test]
+fn test_fail_level() {
+    assert_eq!(status(&[], "x\n", &[]), 0);
+    assert_eq!(status(&[], "not x\n", &[]), 1);
+    assert_eq!(status(&["--fail-level", "convention"], "not x\n", &[]), 1);
+    assert_eq!(status(&["--fail-level", "warning"], "not x\n", &[]), 0);
+    assert_eq!(status(&["--fail-level", "W"], "def (\n", &[]), 1);
+}
+
+#[test]
+fn test_disabled_and_corrected_offenses() {
+    assert_eq!(status(&[], "not x # rubocop:disable Style/Not\n", &[]), 0);
+    assert_eq!(status(&["-a"], "not x\n", &[]), 0);
+}
+
+#[test]
+fn test_errors() {
+    let error: Error = "error".into();
+    assert_eq!(status(&[], "x\n", &[error]), 2);
+    let error: Error = "error".into();
+    assert_eq!(status(&["--diff"], "not x\n", &[error]), 2);
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(status(&["--diff"], "not x\n", &[]), 1);
+    assert_eq!(status(&["--diff"], "x\n", &[]), 0);
+    assert_eq!(
+        status(&["--diff", "--fail-level", "fatal"], "not x\n", &[]),
+        1
+    );
+}
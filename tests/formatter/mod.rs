@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use rubocop::*;
+
+use crate::common::*;
+
+fn format(name: &str, files: &[(source::DecodedInput, Vec<cop::Offense>)]) -> String {
+    let formatter = formatter_for(name, true, cop::Severity::Refactor).unwrap();
+    let mut out = Vec::new();
+    formatter.format(&mut out, files).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_clang_formatter() {
+    let files = inspect(
+        Path::new(""),
+        &[("a.rb", "not x # rubocop:disable Style/Not\nnot y\n")],
+    );
+    let output = format("clang", &files);
+    let expected = "\
+a.rb:2:1: C: [Correctable] Style/Not: Use `!` instead of `not`.
+not y
+^^^
+
+1 file(s) inspected, 1 offense(s) detected, 1 offense(s) autocorrectable
+";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_json_formatter() {
+    let files = inspect(Path::new(""), &[("a.rb", "not x\n"), ("b.rb", "x\n")]);
+    let output = format("json", &files);
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let metadata = &json["metadata"];
+    assert_eq!(metadata["rubocop_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(metadata["ruby_engine"], "ruby");
+    assert!(metadata["ruby_version"].is_string());
+    assert!(metadata["ruby_platform"].is_string());
+
+    assert_eq!(
+        json["files"],
+        serde_json::json!([
+            {
+                "path": "a.rb",
+                "offenses": [
+                    {
+                        "severity": "convention",
+                        "message": "Use `!` instead of `not`.",
+                        "cop_name": "Style/Not",
+                        "corrected": false,
+                        "correctable": true,
+                        "location": {
+                            "start_line": 1,
+                            "start_column": 1,
+                            "last_line": 1,
+                            "last_column": 3,
+                            "length": 3,
+                            "line": 1,
+                            "column": 1,
+                        },
+                        "corrections": [
+                            {
+                                "location": {
+                                    "start_line": 1,
+                                    "start_column": 1,
+                                    "last_line": 1,
+                                    "last_column": 4,
+                                    "length": 4,
+                                    "line": 1,
+                                    "column": 1,
+                                },
+                                "replacement": "!",
+                            },
+                        ],
+                    },
+                ],
+            },
+            {
+                "path": "b.rb",
+                "offenses": [],
+            },
+        ])
+    );
+
+    assert_eq!(
+        json["summary"],
+        serde_json::json!({
+            "offense_count": 1,
+            "target_file_count": 2,
+            "inspected_file_count": 2,
+        })
+    );
+}
+
+#[test]
+fn test_sarif_formatter() {
+    let files = inspect(Path::new(""), &[("lib/a.rb", "x = 1\nnot x\n")]);
+    let output = format("sarif", &files);
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(json["version"], "2.1.0");
+    let runs = json["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+
+    let driver = &runs[0]["tool"]["driver"];
+    assert_eq!(driver["name"], "RuboCop");
+    let rules = driver["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), default::cops().len());
+    let index = rules.iter().position(|r| r["id"] == "Style/Not").unwrap();
+    assert_eq!(
+        rules[index]["shortDescription"]["text"],
+        "Use ! instead of not."
+    );
+
+    assert_eq!(
+        runs[0]["results"],
+        serde_json::json!([
+            {
+                "ruleId": "Style/Not",
+                "level": "note",
+                "message": { "text": "Use `!` instead of `not`." },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "lib/a.rb" },
+                        "region": {
+                            "startLine": 2,
+                            "startColumn": 1,
+                            "endLine": 2,
+                            "endColumn": 4,
+                        },
+                    },
+                }],
+                "ruleIndex": index,
+                "fixes": [{
+                    "description": { "text": "Autocorrect Style/Not" },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": "lib/a.rb" },
+                        "replacements": [{
+                            "deletedRegion": {
+                                "startLine": 2,
+                                "startColumn": 1,
+                                "endLine": 2,
+                                "endColumn": 5,
+                            },
+                            "insertedContent": { "text": "!" },
+                        }],
+                    }],
+                }],
+            },
+        ])
+    );
+}
+
+#[test]
+fn test_junit_formatter() {
+    let files = inspect(Path::new(""), &[("lib/a.rb", "not x\n")]);
+    let output = format("junit", &files);
+    let tests = default::cops().len();
+    assert!(output.starts_with(&format!(
+        "\
+<?xml version='1.0'?>
+<testsuites>
+  <testsuite name='rubocop' tests='{tests}' failures='1'>
+"
+    )));
+    assert!(output.contains(
+        "    <testcase classname='lib.a' name='Style/BeginBlock'/>
+"
+    ));
+    assert!(output.contains(
+        "    <testcase classname='lib.a' name='Style/Not'>
+      <failure type='Style/Not' message='Use `!` instead of `not`.'>
+        lib/a.rb:1:1
+      </failure>
+    </testcase>
+"
+    ));
+    assert!(output.ends_with("  </testsuite>\n</testsuites>\n"));
+}
+
+#[test]
+fn test_checkstyle_formatter() {
+    let files = inspect(
+        Path::new(""),
+        &[("a.rb", "not x\nputs \"<y>\"\n"), ("b.rb", "x\n")],
+    );
+    let output = format("checkstyle", &files);
+    let expected = "\
+<?xml version='1.0'?>
+<checkstyle>
+  <file name='a.rb'>
+    <error line='1' column='1' severity='info' message='Use `!` instead of `not`.' source='Style/Not'/>
+    <error line='2' column='6' severity='info' message='Prefer single-quoted strings when you don&apos;t need string interpolation or special symbols.' source='Style/StringLiterals'/>
+  </file>
+  <file name='b.rb'>
+  </file>
+</checkstyle>
+";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_github_formatter() {
+    let files = inspect(Path::new(""), &[("a,b.rb", "not x\n")]);
+    let output = format("github", &files);
+    let expected = "::error file=a%2Cb.rb,line=1,col=1::Style/Not: Use `!` instead of `not`.\n";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_format_options() {
+    let args = [
+        "-o", "a.txt", "-f", "json", "--out", "b.json", "-f", "github",
+    ];
+    let options = Options::parse(args.map(String::from)).unwrap();
+    let formats = [
+        ("clang".to_string(), Some("a.txt".to_string())),
+        ("json".to_string(), Some("b.json".to_string())),
+        ("github".to_string(), None),
+    ];
+    assert_eq!(options.formats, formats);
+}
+
+#[test]
+fn test_clang_formatter_multiline_offense() {
+    let files = inspect(Path::new(""), &[("a.rb", "x = \"a\nb\"\n")]);
+    let output = format("clang", &files);
+    let expected = "\
+a.rb:1:5: C: [Correctable] Style/StringLiterals: Prefer single-quoted strings when you don't need string interpolation or special symbols.
+x = \"a ...
+    ^^
+";
+    assert!(output.starts_with(expected), "{output}");
+}
+
+#[test]
+fn test_github_formatter_fail_level() {
+    let files = inspect(Path::new(""), &[("a.rb", "not x\n")]);
+    let formatter = formatter_for("github", true, cop::Severity::Warning).unwrap();
+    let mut out = Vec::new();
+    formatter.format(&mut out, &files).unwrap();
+    let expected = "::warning file=a.rb,line=1,col=1::Style/Not: Use `!` instead of `not`.\n";
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+#[test]
+fn test_fail_level_option() {
+    let options = Options::parse(["--fail-level", "W"].map(String::from)).unwrap();
+    assert_eq!(options.fail_level(), cop::Severity::Warning);
+    let options = Options::parse(["--fail-level", "error"].map(String::from)).unwrap();
+    assert_eq!(options.fail_level(), cop::Severity::Error);
+    assert_eq!(Options::default().fail_level(), cop::Severity::Refactor);
+    assert!(Options::parse(["--fail-level", "x"].map(String::from)).is_err());
+}
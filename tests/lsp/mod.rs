@@ -0,0 +1,186 @@
+use std::io::{BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+use rubocop::lsp::{read_message, write_message};
+use serde_json::{json, Value};
+
+use crate::common::*;
+
+struct Client {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+}
+
+impl Client {
+    fn start() -> Client {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_rubocop"))
+            .arg("--lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = process.stdin.take().unwrap();
+        let stdout = BufReader::new(process.stdout.take().unwrap());
+        Client {
+            process,
+            stdin,
+            stdout,
+            next_id: 1,
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+        let response = self.receive();
+        assert_eq!(response["id"], id);
+        response
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.stdin, &message).unwrap();
+    }
+
+    fn send_body(&mut self, body: &str) {
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        read_message(&mut self.stdout).unwrap().unwrap()
+    }
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+    json!({
+        "start": { "line": start.0, "character": start.1 },
+        "end": { "line": end.0, "character": end.1 },
+    })
+}
+
+#[test]
+fn test_language_server() {
+    let dir = temp_dir("lsp");
+    let config = "Style/StringLiterals:\n  Enabled: false\n";
+    write(&dir.join(".rubocop.yml"), config);
+    let uri = format!("file://{}", dir.join("foo.rb").display());
+
+    let mut client = Client::start();
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+    let capabilities = &response["result"]["capabilities"];
+    assert_eq!(capabilities["documentFormattingProvider"], true);
+    client.notify("initialized", json!({}));
+
+    let text = "puts \"é\"; not x\n";
+    let document = json!({ "uri": uri, "languageId": "ruby", "version": 1, "text": text });
+    client.notify("textDocument/didOpen", json!({ "textDocument": document }));
+    let notification = client.receive();
+    assert_eq!(notification["method"], "textDocument/publishDiagnostics");
+    let diagnostic = json!({
+        "range": range((0, 10), (0, 13)),
+        "severity": 3,
+        "code": "Style/Not",
+        "source": "RuboCop",
+        "message": "Use `!` instead of `not`.",
+    });
+    let expected = json!({ "uri": uri, "diagnostics": [diagnostic] });
+    assert_eq!(notification["params"], expected);
+
+    let params = json!({
+        "textDocument": { "uri": uri },
+        "range": range((0, 0), (0, 0)),
+        "context": { "diagnostics": [] },
+    });
+    let response = client.request("textDocument/codeAction", params);
+    let edit = json!({ "range": range((0, 10), (0, 14)), "newText": "!" });
+    let expected = json!([{
+        "title": "Autocorrect Style/Not",
+        "kind": "quickfix",
+        "isPreferred": true,
+        "diagnostics": [diagnostic],
+        "edit": { "changes": { &uri: [edit] } },
+    }]);
+    assert_eq!(response["result"], expected);
+
+    let params = json!({ "textDocument": { "uri": uri }, "options": {} });
+    let response = client.request("textDocument/formatting", params);
+    let edit = json!({ "range": range((0, 0), (1, 0)), "newText": "puts \"é\"; !x\n" });
+    assert_eq!(response["result"], json!([edit]));
+
+    let changes = json!([{ "text": "x\n" }]);
+    let document = json!({ "uri": uri, "version": 2 });
+    let params = json!({ "textDocument": document, "contentChanges": changes });
+    client.notify("textDocument/didChange", params);
+    let notification = client.receive();
+    let expected = json!({ "uri": uri, "diagnostics": [] });
+    assert_eq!(notification["params"], expected);
+
+    let response = client.request("textDocument/hover", json!({}));
+    assert_eq!(response["error"]["code"], -32601);
+
+    let response = client.request("shutdown", Value::Null);
+    assert_eq!(response["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+}
+
+#[test]
+fn test_unsafe_code_actions() {
+    let dir = temp_dir("lsp-unsafe");
+    let config = "Style/RedundantFilterChain:\n  Enabled: true\n";
+    write(&dir.join(".rubocop.yml"), config);
+    let uri = format!("file://{}", dir.join("foo.rb").display());
+
+    let mut client = Client::start();
+    client.request("initialize", json!({ "capabilities": {} }));
+    let text = "arr.select { |y| y }.any?\nnot x\n";
+    let document = json!({ "uri": uri, "languageId": "ruby", "version": 1, "text": text });
+    client.notify("textDocument/didOpen", json!({ "textDocument": document }));
+    client.receive();
+
+    let params = json!({
+        "textDocument": { "uri": uri },
+        "range": range((0, 0), (1, 0)),
+        "context": { "diagnostics": [] },
+    });
+    let response = client.request("textDocument/codeAction", params);
+    let actions = response["result"].as_array().unwrap().iter();
+    let actions = actions.map(|a| (a["title"].as_str().unwrap(), a["isPreferred"].clone()));
+    let actions = actions.collect::<Vec<_>>();
+    assert_eq!(
+        actions,
+        [
+            (
+                "Autocorrect Style/RedundantFilterChain (unsafe)",
+                json!(false)
+            ),
+            ("Autocorrect Style/Not", json!(true)),
+        ]
+    );
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+}
+
+#[test]
+fn test_malformed_message() {
+    let mut client = Client::start();
+    client.send_body("{bad json");
+    let response = client.receive();
+    assert_eq!(response["id"], Value::Null);
+    assert_eq!(response["error"]["code"], -32700);
+
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+    assert!(response["result"]["capabilities"].is_object());
+
+    client.request("shutdown", Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+}
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+
+use rubocop::*;
+
+use crate::common::*;
+
+fn cached_files(dir: &Path) -> Vec<PathBuf> {
+    let dirs = std::fs::read_dir(dir.join("rubocop_cache")).unwrap();
+    let dirs = dirs.map(|entry| entry.unwrap().path());
+    let files = dirs.flat_map(|dir| std::fs::read_dir(dir).unwrap());
+    files.map(|entry| entry.unwrap().path()).collect()
+}
+
+fn messages(options: &Options) -> Vec<String> {
+    let runner = Runner::new(options, default::cops());
+    let (files, errors) = runner.run();
+    assert!(errors.is_empty());
+    let offenses = files.iter().flat_map(|(_, offenses, _)| offenses);
+    offenses.map(|o| o.message.to_string()).collect()
+}
+
+#[test]
+fn test_cached_results_are_used() {
+    let dir = temp_dir("result-cache");
+    let cache_root = dir.join("cache");
+    std::fs::write(dir.join("foo.rb"), "not x\n").unwrap();
+
+    let mut options = Options {
+        paths: vec![dir.join("foo.rb").to_string_lossy().into_owned()],
+        cache_root: Some(cache_root.to_string_lossy().into_owned()),
+        ..Default::default()
+    };
+    let expected = ["Use `!` instead of `not`."];
+    assert_eq!(messages(&options), expected);
+
+    let cached = cached_files(&cache_root);
+    assert_eq!(cached.len(), 1);
+    let contents = std::fs::read_to_string(&cached[0]).unwrap();
+    let contents = contents.replace("instead of", "rather than");
+    std::fs::write(&cached[0], contents).unwrap();
+    assert_eq!(messages(&options), ["Use `!` rather than `not`."]);
+
+    options.cache = Some(false);
+    assert_eq!(messages(&options), expected);
+
+    // Changing the source invalidates the results.
+    options.cache = None;
+    std::fs::write(dir.join("foo.rb"), "not y\n").unwrap();
+    assert_eq!(messages(&options), expected);
+    assert_eq!(cached_files(&cache_root).len(), 2);
+}
+
+#[test]
+fn test_results_of_other_sources_are_ignored() {
+    let dir = temp_dir("result-cache-source");
+    let cache_root = dir.join("cache");
+    std::fs::write(dir.join("foo.rb"), "not x\n").unwrap();
+
+    let options = Options {
+        paths: vec![dir.join("foo.rb").to_string_lossy().into_owned()],
+        cache_root: Some(cache_root.to_string_lossy().into_owned()),
+        ..Default::default()
+    };
+    let expected = ["Use `!` instead of `not`."];
+    assert_eq!(messages(&options), expected);
+
+    // As if the results were saved for another source with the same key.
+    let cached = cached_files(&cache_root);
+    let contents = std::fs::read_to_string(&cached[0]).unwrap();
+    let mut value: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    value["source_hash"] = "0000000000000000".into();
+    value["offenses"][0]["message"] = "Use `!`.".into();
+    std::fs::write(&cached[0], value.to_string()).unwrap();
+    assert_eq!(messages(&options), expected);
+}
+
+#[test]
+fn test_cache_options() {
+    let args = ["--cache", "false", "--cache-root", "tmp"];
+    let options = Options::parse(args.map(String::from)).unwrap();
+    assert!(!options.is_cache_enabled());
+    assert_eq!(options.cache_root.as_deref(), Some("tmp"));
+    assert!(Options::default().is_cache_enabled());
+    assert!(Options::parse(["--cache", "no"].map(String::from)).is_err());
+}
+
+#[test]
+fn test_prune() {
+    let dir = temp_dir("result-cache-prune");
+    let cache = ResultCache::new(&dir, default::cops());
+    let key = cache.key(&dir, "foo.rb", Config::new(None), default::cops(), b"");
+    let current = key.parent().unwrap();
+    let old = dir.join("rubocop_cache/0000000000000000");
+
+    let start = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    let files = ["0000000000000000/a", "0000000000000000/b", "c", "d", "e"];
+    for (i, name) in files.iter().enumerate() {
+        let path = match name.split_once('/') {
+            Some(_) => dir.join("rubocop_cache").join(name),
+            None => current.join(name),
+        };
+        write(&path, "[]");
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        let modified = start + std::time::Duration::from_secs(i as u64);
+        file.set_modified(modified).unwrap();
+    }
+
+    cache.prune(5).unwrap();
+    assert_eq!(cached_files(&dir).len(), 5);
+
+    // The oldest half and one more are removed, with the emptied directory.
+    cache.prune(4).unwrap();
+    let names = cached_files(&dir);
+    let names = names
+        .iter()
+        .map(|path| path.file_name().unwrap().to_owned());
+    let mut names = names.collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["d", "e"]);
+    assert!(!old.exists());
+
+    let empty = temp_dir("result-cache-prune-empty");
+    ResultCache::new(&empty, default::cops()).prune(0).unwrap();
+}
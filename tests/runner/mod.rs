@@ -0,0 +1,453 @@
+use std::borrow::Cow;
+
+use lib_ruby_parser::nodes::{Float, Int};
+use rubocop::cop::{AutoCorrector, Base, Context, Name, Offense};
+use rubocop::source::Corrector;
+use rubocop::*;
+
+use crate::common::*;
+
+config!(
+    "
+    Style/ExactRegexpMatch:
+      Enabled: true
+    "
+);
+
+#[test]
+fn test_autocorrect_until_no_changes() {
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let config = config().clone().into();
+    let config = Config::new(Some(&config));
+
+    let source = "x =~ Regexp.new(/\\Ay\\z/)\nputs \"z\"\n";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (input, offenses, output) = result.unwrap();
+
+    assert_eq!(input.as_shared_bytes(), source.as_bytes());
+    assert_eq!(output.unwrap(), b"x == 'y'\nputs 'z'\n");
+
+    let offenses = offenses
+        .iter()
+        .map(|o| (o.cop_name, o.loc.begin, o.loc.end));
+    let offenses = offenses.collect::<Vec<_>>();
+    assert_eq!(
+        offenses,
+        [
+            ("Style/RedundantRegexpConstructor", 5, 24),
+            ("Style/StringLiterals", 30, 33),
+            ("Style/ExactRegexpMatch", 0, 24),
+        ]
+    );
+}
+
+#[test]
+fn test_no_autocorrect() {
+    let options = Options::default();
+    let config = Config::new(None);
+
+    let source = "not y\n";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (_, offenses, output) = result.unwrap();
+
+    assert!(output.is_none());
+    assert_eq!(offenses.len(), 1);
+    assert!(!offenses[0].corrected);
+}
+
+#[test]
+fn test_offense_edits() {
+    let options = Options::default();
+    let config = Config::new(None);
+
+    let source = "not x\nnot y\n";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (_, offenses, _) = result.unwrap();
+
+    let edits = offenses.iter().map(|o| {
+        let edits = o
+            .edits
+            .iter()
+            .map(|(loc, s)| (loc.begin, loc.end, s.as_str()));
+        edits.collect::<Vec<_>>()
+    });
+    let edits = edits.collect::<Vec<_>>();
+    assert_eq!(edits, [[(0, 4, "!")], [(6, 10, "!")]]);
+}
+
+#[test]
+fn test_clobbering_corrections_are_deferred() {
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let config = serde_yaml::from_str("Style/RedundantFilterChain: { Enabled: true }");
+    let config = config.unwrap();
+    let config = Config::new(Some(&config));
+
+    let source = "not arr.select { |y| y }.empty?\n";
+    let runner = Runner::new(&options, default::cops());
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let (_, offenses, output) = result.unwrap();
+
+    assert_eq!(output.unwrap(), b"arr.none? { |y| y }\n");
+
+    let offenses = offenses.iter().map(|o| (o.cop_name, o.corrected));
+    let offenses = offenses.collect::<Vec<_>>();
+    assert_eq!(
+        offenses,
+        [("Style/Not", true), ("Style/RedundantFilterChain", true)]
+    );
+}
+
+#[test]
+fn test_unsafe_corrections_need_autocorrect_all() {
+    let config = serde_yaml::from_str("Style/RedundantFilterChain: { Enabled: true }");
+    let config = config.unwrap();
+    let config = Config::new(Some(&config));
+    let source = "arr.select { |y| y }.any?\nnot x\n";
+
+    let inspect = |autocorrect| {
+        let options = Options {
+            autocorrect,
+            ..Default::default()
+        };
+        let runner = Runner::new(&options, default::cops());
+        let result = runner.inspect_source(config, "test.rb".into(), source.into());
+        let (_, offenses, output) = result.unwrap();
+        let offenses = offenses
+            .iter()
+            .map(|o| (o.cop_name, o.correctable, o.corrected));
+        (offenses.collect::<Vec<_>>(), output.unwrap())
+    };
+
+    let (offenses, output) = inspect(Autocorrect::Safe);
+    assert_eq!(output, b"arr.select { |y| y }.any?\n!x\n");
+    assert_eq!(
+        offenses,
+        [
+            ("Style/Not", true, true),
+            ("Style/RedundantFilterChain", true, false),
+        ]
+    );
+
+    let (offenses, output) = inspect(Autocorrect::All);
+    assert_eq!(output, b"arr.any? { |y| y }\n!x\n");
+    assert!(offenses.iter().all(|&(_, _, corrected)| corrected));
+}
+
+#[test]
+fn test_find_ruby_files() {
+    let dir = temp_dir("find-files");
+    let files = [
+        (".rubocop.yml", ""),
+        ("Gemfile", ""),
+        ("foo.gemspec", ""),
+        ("lib/foo.rb", ""),
+        ("lib/foo.py", ""),
+        ("bin/foo", "#!/usr/bin/env ruby\n"),
+        ("bin/bar", "#!/usr/bin/env python\n"),
+        ("vendor/bundle/foo.rb", ""),
+    ];
+    for (path, contents) in files {
+        write(&dir.join(path), contents);
+    }
+
+    let dir = dir.to_string_lossy().into_owned();
+    let options = Options {
+        cache: Some(false),
+        paths: vec![dir.clone()],
+        ..Default::default()
+    };
+    let runner = Runner::new(&options, default::cops());
+    let (files, errors) = runner.run();
+    assert!(errors.is_empty());
+
+    let names = files.iter().map(|(input, _, _)| input.name.clone());
+    let mut names = names.collect::<Vec<_>>();
+    names.sort();
+    let sep = std::path::MAIN_SEPARATOR;
+    let expected = ["Gemfile", "bin/foo", "foo.gemspec", "lib/foo.rb"];
+    let expected =
+        expected.map(|name| format!("{dir}{sep}{}", name.replace('/', &sep.to_string())));
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn test_errors_are_collected() {
+    let dir = temp_dir("errors");
+    write(&dir.join("lib/good.rb"), "not x\n");
+    write(&dir.join("lib/bad.rb"), "def (\n");
+
+    let missing = dir.join("missing.rb").to_string_lossy().into_owned();
+    let options = Options {
+        cache: Some(false),
+        paths: vec![
+            dir.join("lib").to_string_lossy().into_owned(),
+            missing.clone(),
+        ],
+        ..Default::default()
+    };
+    let runner = Runner::new(&options, default::cops());
+    let (files, errors) = runner.run();
+
+    let names = files.iter().map(|(input, _, _)| input.name.clone());
+    let names = names.collect::<Vec<_>>();
+    let expected = ["lib/bad.rb", "lib/good.rb"].map(|name| dir.join(name));
+    assert_eq!(
+        names,
+        expected.map(|path| path.to_string_lossy().into_owned())
+    );
+
+    let (_, offenses, _) = &files[0];
+    let offenses = offenses.iter().map(|o| (o.cop_name, o.severity));
+    let offenses = offenses.collect::<Vec<_>>();
+    assert_eq!(offenses, [("Lint/Syntax", cop::Severity::Fatal)]);
+
+    let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with(&format!("{missing}: ")));
+}
+
+#[test]
+fn test_parallel_inspection() {
+    let dir = temp_dir("parallel");
+    for i in 0..20 {
+        let path = dir.join(format!("lib/{}/foo_{i}.rb", i % 3));
+        write(&path, &"not x\n".repeat(i));
+    }
+
+    let run = |parallel| {
+        let options = Options {
+            cache: Some(false),
+            paths: vec![dir.to_string_lossy().into_owned()],
+            parallel: Some(parallel),
+            ..Default::default()
+        };
+        let runner = Runner::new(&options, default::cops());
+        let (files, errors) = runner.run();
+        assert!(errors.is_empty());
+        let files = files
+            .iter()
+            .map(|(input, offenses, _)| (input.name.clone(), offenses.len()));
+        files.collect::<Vec<_>>()
+    };
+
+    let files = run(true);
+    assert_eq!(files.len(), 20);
+    assert_eq!(files, run(false));
+}
+
+#[test]
+fn test_autocorrect_writes_files() {
+    let dir = temp_dir("autocorrect-files");
+    let config = "Style/RedundantFilterChain:\n  Enabled: true\n";
+    write(&dir.join(".rubocop.yml"), config);
+    let source = "arr.select { |y| y }.any?\nnot x\n";
+
+    let run = |flag: &str| {
+        let path = dir.join("foo.rb");
+        write(&path, source);
+        let path = path.to_string_lossy().into_owned();
+        let args = [flag.into(), "--cache".into(), "false".into(), path.clone()];
+        let options = Options::parse(args).unwrap();
+        let runner = Runner::new(&options, default::cops());
+        let (files, errors) = runner.run();
+        assert!(errors.is_empty());
+        assert_eq!(files.len(), 1);
+        std::fs::read(path).unwrap()
+    };
+
+    assert_eq!(run("-a"), b"arr.select { |y| y }.any?\n!x\n");
+    assert_eq!(run("-A"), b"arr.any? { |y| y }\n!x\n");
+}
+
+#[test]
+fn test_inspect_stdin() {
+    let dir = temp_dir("stdin");
+    write(&dir.join(".rubocop.yml"), "Style/Not:\n  Enabled: false\n");
+
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let runner = Runner::new(&options, default::cops());
+    let path = dir.join("foo.rb").to_string_lossy().into_owned();
+    let source = "not x\nputs \"y\"\n";
+    let result = runner.inspect_stdin(&path, source.into());
+    let (input, offenses, output) = result.unwrap();
+
+    assert_eq!(input.name, path);
+    assert_eq!(input.as_shared_bytes(), source.as_bytes());
+    assert_eq!(output.unwrap(), b"not x\nputs 'y'\n");
+    assert!(!dir.join("foo.rb").exists());
+
+    let offenses = offenses.iter().map(|o| o.cop_name).collect::<Vec<_>>();
+    assert_eq!(offenses, ["Style/StringLiterals"]);
+}
+
+// Cops whose corrections undo each other.
+struct IntToFloat;
+struct FloatToInt;
+// A cop whose corrections never stop changing the source.
+struct Increment;
+// A cop whose offenses have clashing corrections.
+struct Clash;
+
+impl Name for IntToFloat {
+    fn name(&self) -> &'static str {
+        "Test/IntToFloat"
+    }
+}
+
+impl Name for FloatToInt {
+    fn name(&self) -> &'static str {
+        "Test/FloatToInt"
+    }
+}
+
+impl Name for Increment {
+    fn name(&self) -> &'static str {
+        "Test/Increment"
+    }
+}
+
+impl AutoCorrector for IntToFloat {}
+impl AutoCorrector for FloatToInt {}
+impl AutoCorrector for Increment {}
+impl AutoCorrector for Clash {}
+
+impl Name for Clash {
+    fn name(&self) -> &'static str {
+        "Test/Clash"
+    }
+}
+
+impl Base for Clash {
+    fn on_new_investigation(&self, ctx: &mut Context, corrector: &mut Corrector) {
+        let loc = lib_ruby_parser::Loc { begin: 0, end: 1 };
+        if ctx.source(loc) != "x" {
+            return;
+        }
+        for replacement in ["y", "z"] {
+            add_offense!(self, ctx, corrector, loc, "Clash.", {
+                corrector.replace(loc, replacement);
+            });
+        }
+    }
+}
+
+impl Base for IntToFloat {
+    fn on_int(&self, ctx: &mut Context, corrector: &mut Corrector, node: &Int) {
+        add_offense!(self, ctx, corrector, node.expression_l, "Use a float.", {
+            corrector.insert_after(node.expression_l, ".0");
+        });
+    }
+}
+
+impl Base for FloatToInt {
+    fn on_float(&self, ctx: &mut Context, corrector: &mut Corrector, node: &Float) {
+        add_offense!(
+            self,
+            ctx,
+            corrector,
+            node.expression_l,
+            "Use an integer.",
+            {
+                let value = node.value.trim_end_matches(".0").to_string();
+                corrector.replace(node.expression_l, value);
+            }
+        );
+    }
+}
+
+impl Base for Increment {
+    fn on_int(&self, ctx: &mut Context, corrector: &mut Corrector, node: &Int) {
+        add_offense!(self, ctx, corrector, node.expression_l, "Increment.", {
+            let value = node.value.parse::<u64>().unwrap() + 1;
+            corrector.replace(node.expression_l, value.to_string());
+        });
+    }
+}
+
+fn correction_loop(cops: &[&dyn Base], source: &str) -> InfiniteCorrectionLoop {
+    let options = Options {
+        autocorrect: Autocorrect::All,
+        ..Default::default()
+    };
+    let config = cops
+        .iter()
+        .map(|cop| format!("{}: {{ Enabled: true }}\n", cop.name()));
+    let config = serde_yaml::from_str(&config.collect::<String>()).unwrap();
+    let config = Config::new(Some(&config));
+
+    let runner = Runner::new(&options, cops);
+    let result = runner.inspect_source(config, "test.rb".into(), source.into());
+    let error = result.err().unwrap();
+    *error.downcast::<InfiniteCorrectionLoop>().unwrap()
+}
+
+#[test]
+fn test_infinite_correction_loop() {
+    let error = correction_loop(&[&IntToFloat, &FloatToInt], "x = 1\n");
+    assert_eq!(error.path, "test.rb");
+    assert_eq!(
+        error.cop_names,
+        [vec!["Test/IntToFloat"], vec!["Test/FloatToInt"]]
+    );
+    assert_eq!(
+        error.to_string(),
+        "Infinite loop detected in test.rb and caused by Test/IntToFloat -> Test/FloatToInt"
+    );
+}
+
+#[test]
+fn test_too_many_corrections() {
+    let error = correction_loop(&[&Increment], "x = 1\n");
+    assert_eq!(error.cop_names.len(), 200);
+    assert!(error
+        .cop_names
+        .iter()
+        .all(|names| names == &["Test/Increment"]));
+}
+
+#[test]
+fn test_clashing_corrections_keep_edits() {
+    let cops: &[&dyn Base] = &[&Clash];
+    let config = serde_yaml::from_str("Test/Clash: { Enabled: true }").unwrap();
+    let inspect = |autocorrect| {
+        let options = Options {
+            autocorrect,
+            ..Default::default()
+        };
+        let runner = Runner::new(&options, cops);
+        let config = Config::new(Some(&config));
+        let result = runner.inspect_source(config, "test.rb".into(), "x\n".into());
+        let (_, offenses, output) = result.unwrap();
+        (offenses, output)
+    };
+    let summary = |offenses: &[Offense]| {
+        let offenses = offenses.iter().map(|o| {
+            let edits = o.edits.iter().map(|(_, s)| s.as_str());
+            (o.corrected, edits.collect::<String>())
+        });
+        offenses
+            .map(|(c, e)| format!("{c} {e}"))
+            .collect::<Vec<_>>()
+    };
+
+    let (offenses, output) = inspect(Autocorrect::Off);
+    assert_eq!(summary(&offenses), ["false y", "false z"]);
+    assert!(output.is_none());
+
+    let (offenses, output) = inspect(Autocorrect::All);
+    // The clashing offense is gone once the first correction is applied.
+    assert_eq!(summary(&offenses), ["true y"]);
+    assert_eq!(output.unwrap(), b"y\n");
+}
@@ -1 +1,12 @@
+mod auto_gen_config;
+mod comment_config;
+mod common;
+mod config;
 mod cop;
+mod corrector;
+mod diff;
+mod exit_status;
+mod formatter;
+mod lsp;
+mod result_cache;
+mod runner;